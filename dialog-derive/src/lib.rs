@@ -0,0 +1,331 @@
+// Copyright (C) 2019 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: MIT
+
+#![warn(missing_docs, rust_2018_compatibility, rust_2018_idioms, unused)]
+
+//! Derive macro for the `dialog` crate.
+//!
+//! This crate provides the [`Dialog`][] derive macro that implements the `dialog::FromDialog`
+//! trait.  It should not be used directly.  Instead, enable the `derive` feature of the `dialog`
+//! crate and use the re-exported `dialog::Dialog` macro.
+//!
+//! [`Dialog`]: derive.Dialog.html
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+
+/// Derives the `dialog::FromDialog` trait for a struct or an enum.
+///
+/// For a struct with named fields, the derived implementation shows a `dialog::Form` with one
+/// text field per struct field.  The values are converted using the `FromStr` implementation of
+/// the field type.  If a value cannot be converted, the form is shown again with an error
+/// message.  For generic structs, the derived implementation requires that the field types
+/// implement `FromStr` and that their errors implement `Display`.
+///
+/// For an enum without fields, the derived implementation shows a `dialog::Menu` with one item per
+/// variant and returns the selected variant.  If the backend returns an invalid menu index, an
+/// error is returned.
+///
+/// Both implementations return `None` if the user cancels the dialog.
+///
+/// # Attributes
+///
+/// The dialog box can be customized with `#[dialog(...)]` attributes.  These keys are supported
+/// for the struct or enum:
+/// - `text = "..."`: the text of the dialog box
+/// - `title = "..."`: the title of the dialog box
+///
+/// These keys are supported for the struct fields:
+/// - `label = "..."`: the label of the form field (default: the field name with spaces instead of
+///   underscores)
+/// - `default = "..."`: the default value of the form field
+/// - `password`: hide the input, for example for passwords
+///
+/// This key is supported for the enum variants:
+/// - `label = "..."`: the label of the menu item (default: the variant name with spaces between
+///   the words)
+///
+/// # Example
+///
+/// ```no_run
+/// use dialog::{Dialog, FromDialog};
+///
+/// #[derive(Dialog)]
+/// #[dialog(text = "Please enter the connection settings", title = "Connection")]
+/// struct Connection {
+///     #[dialog(default = "localhost")]
+///     host: String,
+///     #[dialog(default = "5432")]
+///     port: u16,
+///     #[dialog(label = "User name")]
+///     user: String,
+///     #[dialog(password)]
+///     password: String,
+/// }
+///
+/// #[derive(Dialog)]
+/// #[dialog(text = "Please choose the log level")]
+/// enum LogLevel {
+///     Error,
+///     Warning,
+///     #[dialog(label = "Information")]
+///     Info,
+/// }
+///
+/// let connection = Connection::from_dialog().expect("Could not display dialog box");
+/// let level = LogLevel::from_dialog().expect("Could not display dialog box");
+/// ```
+#[proc_macro_derive(Dialog, attributes(dialog))]
+pub fn derive_dialog(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: &syn::DeriveInput) -> syn::Result<TokenStream> {
+    let options = Options::parse(&input.attrs, &["text", "title"])?;
+    let body = match input.data {
+        syn::Data::Struct(ref data) => expand_struct(input, data, &options)?,
+        syn::Data::Enum(ref data) => expand_enum(input, data, &options)?,
+        syn::Data::Union(ref data) => {
+            return Err(syn::Error::new(
+                data.union_token.span,
+                "Dialog cannot be derived for unions",
+            ))
+        }
+    };
+
+    let mut generics = input.generics.clone();
+    if let syn::Data::Struct(ref data) = input.data {
+        if generics.type_params().next().is_some() {
+            add_field_bounds(&mut generics, &data.fields);
+        }
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::dialog::FromDialog for #name #ty_generics #where_clause {
+            fn from_dialog_with<__B>(
+                backend: impl ::std::convert::AsRef<__B>,
+            ) -> ::dialog::Result<::std::option::Option<Self>>
+            where
+                __B: ::dialog::backends::Backend + ?Sized,
+            {
+                #body
+            }
+        }
+    })
+}
+
+fn expand_struct(
+    input: &syn::DeriveInput,
+    data: &syn::DataStruct,
+    options: &Options,
+) -> syn::Result<TokenStream> {
+    let fields = match data.fields {
+        syn::Fields::Named(ref fields) => &fields.named,
+        _ => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "Dialog can only be derived for structs with named fields",
+            ))
+        }
+    };
+    if fields.is_empty() {
+        return Err(syn::Error::new(
+            input.ident.span(),
+            "Dialog cannot be derived for structs without fields",
+        ));
+    }
+
+    let mut form_fields = Vec::new();
+    let mut values = Vec::new();
+    for (i, field) in fields.iter().enumerate() {
+        let field_options = Options::parse(&field.attrs, &["label", "default", "password"])?;
+        let ident = field
+            .ident
+            .as_ref()
+            .expect("Named field without identifier");
+        let ty = &field.ty;
+        let label = field_options
+            .label
+            .unwrap_or_else(|| field_label(&ident.unraw().to_string()));
+        let constructor = if field_options.password {
+            quote! { ::dialog::Field::password(#label) }
+        } else {
+            quote! { ::dialog::Field::text(#label) }
+        };
+        form_fields.push(match field_options.default {
+            Some(default) => quote! { #constructor.with_value(#default) },
+            None => constructor,
+        });
+        values.push(quote! {
+            #ident: <#ty as ::std::str::FromStr>::from_str(&values[#i])
+                .map_err(|err| ::std::format!("{}: {}", #label, err))?
+        });
+    }
+
+    let form = new_dialog(quote! { form }, quote! { ::dialog::Form }, options);
+    Ok(quote! {
+        #form
+        #(form.field(#form_fields);)*
+        form.convert_with(backend, |values| {
+            ::std::result::Result::Ok(Self {
+                #(#values,)*
+            })
+        })
+    })
+}
+
+fn expand_enum(
+    input: &syn::DeriveInput,
+    data: &syn::DataEnum,
+    options: &Options,
+) -> syn::Result<TokenStream> {
+    if data.variants.is_empty() {
+        return Err(syn::Error::new(
+            input.ident.span(),
+            "Dialog cannot be derived for enums without variants",
+        ));
+    }
+
+    let mut labels = Vec::new();
+    let mut arms = Vec::new();
+    for (i, variant) in data.variants.iter().enumerate() {
+        if !variant.fields.is_empty() {
+            return Err(syn::Error::new(
+                variant.fields.span(),
+                "Dialog can only be derived for enums without fields",
+            ));
+        }
+        let variant_options = Options::parse(&variant.attrs, &["label"])?;
+        let ident = &variant.ident;
+        labels.push(
+            variant_options
+                .label
+                .unwrap_or_else(|| variant_label(&ident.unraw().to_string())),
+        );
+        arms.push(quote! {
+            #i => ::std::result::Result::Ok(::std::option::Option::Some(Self::#ident))
+        });
+    }
+
+    let menu = new_dialog(quote! { menu }, quote! { ::dialog::Menu }, options);
+    Ok(quote! {
+        #menu
+        #(menu.item(#labels);)*
+        let index = match ::dialog::DialogBox::show_with(&menu, backend)? {
+            ::std::option::Option::Some(index) => index,
+            ::std::option::Option::None => {
+                return ::std::result::Result::Ok(::std::option::Option::None);
+            }
+        };
+        // Backends implemented outside of the dialog crate might return an invalid index
+        match index {
+            #(#arms,)*
+            _ => ::std::result::Result::Err(::dialog::Error::Error(::std::format!(
+                "The backend returned the invalid menu index {}",
+                index
+            ))),
+        }
+    })
+}
+
+/// Adds the bounds that are required to convert the values of the given fields to the where
+/// clause of the given generics.
+fn add_field_bounds(generics: &mut syn::Generics, fields: &syn::Fields) {
+    let predicates = &mut generics.make_where_clause().predicates;
+    for field in fields {
+        let ty = &field.ty;
+        predicates.push(syn::parse_quote! { #ty: ::std::str::FromStr });
+        predicates.push(syn::parse_quote! {
+            <#ty as ::std::str::FromStr>::Err: ::std::fmt::Display
+        });
+    }
+}
+
+/// Returns the statements that create the given dialog box with the text and the title set in the
+/// given options and assign it to the given variable.
+fn new_dialog(var: TokenStream, ty: TokenStream, options: &Options) -> TokenStream {
+    let text = options
+        .text
+        .clone()
+        .unwrap_or_else(|| syn::LitStr::new("", proc_macro2::Span::call_site()));
+    let title = options.title.iter();
+    quote! {
+        let mut #var = #ty::new(#text);
+        #(#var.title(#title);)*
+    }
+}
+
+/// Returns the default label for a field, for example `Host name` for `host_name`.
+fn field_label(name: &str) -> syn::LitStr {
+    let label = capitalize(&name.replace('_', " "));
+    syn::LitStr::new(&label, proc_macro2::Span::call_site())
+}
+
+/// Returns the default label for a variant, for example `Dark red` for `DarkRed`.
+fn variant_label(name: &str) -> syn::LitStr {
+    let mut words = String::new();
+    for c in name.chars() {
+        if c.is_uppercase() && !words.is_empty() {
+            words.push(' ');
+        }
+        words.push(c);
+    }
+    let label = capitalize(&words.to_lowercase());
+    syn::LitStr::new(&label, proc_macro2::Span::call_site())
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// The options set with `#[dialog(...)]` attributes.
+#[derive(Default)]
+struct Options {
+    text: Option<syn::LitStr>,
+    title: Option<syn::LitStr>,
+    label: Option<syn::LitStr>,
+    default: Option<syn::LitStr>,
+    password: bool,
+}
+
+impl Options {
+    /// Parses the `#[dialog(...)]` attributes in the given list, accepting only the given keys.
+    fn parse(attrs: &[syn::Attribute], keys: &[&str]) -> syn::Result<Options> {
+        let mut options = Options::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("dialog")) {
+            attr.parse_nested_meta(|meta| {
+                let key = meta.path.get_ident().map(ToString::to_string);
+                let key = match key {
+                    Some(ref key) if keys.contains(&key.as_str()) => key.as_str(),
+                    _ => {
+                        return Err(meta.error(format!(
+                            "unsupported dialog attribute, expected one of: {}",
+                            keys.join(", ")
+                        )))
+                    }
+                };
+                match key {
+                    "text" => options.text = Some(meta.value()?.parse()?),
+                    "title" => options.title = Some(meta.value()?.parse()?),
+                    "label" => options.label = Some(meta.value()?.parse()?),
+                    "default" => options.default = Some(meta.value()?.parse()?),
+                    "password" => options.password = true,
+                    _ => unreachable!(),
+                }
+                Ok(())
+            })?;
+        }
+        Ok(options)
+    }
+}
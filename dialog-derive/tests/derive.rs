@@ -0,0 +1,156 @@
+// Copyright (C) 2019 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: MIT
+
+//! Tests the derived `FromDialog` implementations using a `Stdio` backend that reads the answers
+//! from a string.
+
+use dialog::backends::{Backend, Catalog, Stdio};
+use dialog::{Choice, Dialog, FromDialog, Menu, Secret};
+
+#[derive(Debug, PartialEq, Dialog)]
+#[dialog(text = "Please enter the connection settings", title = "Connection")]
+struct Connection {
+    #[dialog(default = "localhost")]
+    host: String,
+    #[dialog(default = "5432")]
+    port: u16,
+    #[dialog(label = "User name")]
+    user: String,
+    #[dialog(password)]
+    password: String,
+}
+
+#[derive(Debug, PartialEq, Dialog)]
+struct Pair<T> {
+    first_value: T,
+    second_value: T,
+}
+
+#[derive(Debug, PartialEq, Dialog)]
+#[dialog(text = "Please choose the log level")]
+enum LogLevel {
+    Error,
+    WarningOrWorse,
+    #[dialog(label = "Information")]
+    Info,
+}
+
+/// Shows the dialog for the given type with a `Stdio` backend that reads the given input and
+/// returns the result and the output of the backend.
+fn run<T: FromDialog>(input: &str) -> (Option<T>, String) {
+    let mut output = Vec::new();
+    let result = {
+        let mut stdio = Stdio::with_io(input.as_bytes(), &mut output);
+        stdio.set_catalog(Catalog::english());
+        T::from_dialog_with(&stdio).expect("Could not show dialog box")
+    };
+    (result, String::from_utf8(output).expect("Invalid output"))
+}
+
+#[test]
+fn struct_labels_and_defaults() {
+    let (connection, output) = run::<Connection>("\n\nalice\nsecret\n");
+    assert_eq!(
+        Some(Connection {
+            host: "localhost".to_string(),
+            port: 5432,
+            user: "alice".to_string(),
+            password: "secret".to_string(),
+        }),
+        connection
+    );
+    assert!(output.contains("Connection"));
+    assert!(output.contains("Please enter the connection settings"));
+    assert!(output.contains("Host [default: localhost]: "));
+    assert!(output.contains("Port [default: 5432]: "));
+    assert!(output.contains("User name: "));
+    assert!(output.contains("Password: "));
+}
+
+#[test]
+fn struct_invalid_value() {
+    let (connection, output) = run::<Connection>("\nport\nalice\nsecret\n\n5432\n\nsecret\n");
+    assert_eq!(Some(5432), connection.map(|connection| connection.port));
+    assert!(output.contains("Port: invalid digit found in string"));
+    assert!(output.contains("Port [default: port]: "));
+    assert!(output.contains("User name [default: alice]: "));
+}
+
+#[test]
+fn struct_cancel() {
+    let (connection, _) = run::<Connection>("\n");
+    assert_eq!(None, connection);
+}
+
+#[test]
+fn generic_struct() {
+    let (pair, output) = run::<Pair<i32>>("1\n2\n");
+    assert_eq!(
+        Some(Pair {
+            first_value: 1,
+            second_value: 2,
+        }),
+        pair
+    );
+    assert!(output.contains("First value: "));
+    assert!(output.contains("Second value: "));
+}
+
+#[test]
+fn enum_labels() {
+    let (level, output) = run::<LogLevel>("2\n");
+    assert_eq!(Some(LogLevel::WarningOrWorse), level);
+    assert!(output.contains("Please choose the log level"));
+    assert!(output.contains("1) Error"));
+    assert!(output.contains("2) Warning or worse"));
+    assert!(output.contains("3) Information"));
+}
+
+#[test]
+fn enum_cancel() {
+    let (level, _) = run::<LogLevel>("");
+    assert_eq!(None, level);
+}
+
+/// A backend that returns the given index for all menus.
+struct BadMenu(usize);
+
+impl AsRef<BadMenu> for BadMenu {
+    fn as_ref(&self) -> &Self {
+        self
+    }
+}
+
+impl Backend for BadMenu {
+    fn show_input(&self, _input: &dialog::Input) -> dialog::Result<Option<String>> {
+        Ok(None)
+    }
+
+    fn show_message(&self, _message: &dialog::Message) -> dialog::Result<()> {
+        Ok(())
+    }
+
+    fn show_password(&self, _password: &dialog::Password) -> dialog::Result<Option<Secret>> {
+        Ok(None)
+    }
+
+    fn show_question(&self, _question: &dialog::Question) -> dialog::Result<Choice> {
+        Ok(Choice::Cancel)
+    }
+
+    fn show_menu(&self, _menu: &Menu) -> dialog::Result<Option<usize>> {
+        Ok(Some(self.0))
+    }
+}
+
+#[test]
+fn enum_invalid_index() {
+    assert_eq!(
+        Some(LogLevel::Info),
+        LogLevel::from_dialog_with(BadMenu(2)).unwrap()
+    );
+    match LogLevel::from_dialog_with(BadMenu(3)) {
+        Err(dialog::Error::Error(message)) => assert!(message.contains("invalid menu index 3")),
+        result => panic!("Unexpected result: {:?}", result),
+    }
+}
@@ -0,0 +1,39 @@
+// Copyright (C) 2019 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: MIT
+
+use dialog::{Dialog, FromDialog};
+
+#[derive(Debug, Dialog)]
+#[dialog(text = "Please choose the protocol", title = "Protocol")]
+enum Protocol {
+    Http,
+    Https,
+    #[dialog(label = "FTP")]
+    Ftp,
+}
+
+#[derive(Debug, Dialog)]
+#[dialog(text = "Please enter the server settings", title = "Server")]
+struct Server {
+    #[dialog(default = "localhost")]
+    host: String,
+    #[dialog(default = "8080")]
+    port: u16,
+    #[dialog(label = "Maximum connections", default = "10")]
+    max_connections: usize,
+}
+
+fn main() -> dialog::Result<()> {
+    let protocol = match Protocol::from_dialog()? {
+        Some(protocol) => protocol,
+        None => return Ok(()),
+    };
+    match Server::from_dialog()? {
+        Some(server) => println!(
+            "Connecting to {}:{} using {:?} with at most {} connections",
+            server.host, server.port, protocol, server.max_connections
+        ),
+        None => println!("You do not want to configure a server."),
+    };
+    Ok(())
+}
@@ -0,0 +1,18 @@
+// Copyright (C) 2019 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: MIT
+
+use dialog::{DialogBox, Field};
+
+fn main() -> dialog::Result<()> {
+    let values = dialog::Form::new("Please enter the connection settings")
+        .title("Connection")
+        .field(Field::read_only("Server", "example.org"))
+        .field(Field::text("Username").with_value("guest"))
+        .field(Field::password("Password"))
+        .show()?;
+    match values {
+        Some(values) => println!("Connecting to {} as {}", values[0], values[1]),
+        None => println!("You do not want to connect."),
+    };
+    Ok(())
+}
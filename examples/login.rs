@@ -0,0 +1,16 @@
+// Copyright (C) 2019 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: MIT
+
+use dialog::DialogBox;
+
+fn main() -> dialog::Result<()> {
+    let credentials = dialog::Login::new("Please log in to continue")
+        .title("Login")
+        .username("guest")
+        .show()?;
+    match credentials {
+        Some(credentials) => println!("Welcome back, {}!", credentials.username()),
+        None => println!("You do not want to log in."),
+    };
+    Ok(())
+}
@@ -8,7 +8,7 @@ fn main() -> dialog::Result<()> {
         .title("Password")
         .show()?;
     match password {
-        Some(password) => println!("Your new password is: {}", password),
+        Some(password) => println!("Your new password is: {}", password.expose()),
         None => println!("You do not want to have a password."),
     };
     Ok(())
@@ -0,0 +1,14 @@
+// Copyright (C) 2019 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: MIT
+
+use dialog::DialogBox;
+
+fn main() -> dialog::Result<()> {
+    let notes = dialog::TextInput::new("Please enter your notes")
+        .title("Notes")
+        .default("TODO:\n- ")
+        .show()?;
+
+    println!("Notes: {:?}", notes);
+    Ok(())
+}
@@ -1,16 +1,25 @@
 // Copyright (C) 2019 Robin Krahl <robin.krahl@ireas.org>
 // SPDX-License-Identifier: MIT
 
+use std::ffi;
+use std::io::{self, Read};
+use std::path;
 use std::process;
 
-use crate::{Choice, Error, Input, Message, Password, Question, Result};
+use crate::{
+    Choice, Credentials, Error, FieldKind, Form, Input, License, Login, Menu, Message, Password,
+    ProgramBox, Question, Result, Secret, Tail, TextInput, TextViewer,
+};
 
 /// The `dialog` backend.
 ///
 /// This backend uses the external `dialog` program (not to be confused with this crate also called
-/// `dialog`) to display text-based dialog boxes in the terminal.
+/// `dialog`) to display text-based dialog boxes in the terminal.  The dialog boxes are drawn on the
+/// controlling terminal even if the standard input or output is redirected, and the results are
+/// read from a separate pipe, so this backend can be used in pipelines.
 #[derive(Debug)]
 pub struct Dialog {
+    program: super::Program,
     backtitle: Option<String>,
     width: String,
     height: String,
@@ -20,6 +29,7 @@ impl Dialog {
     /// Creates a new `Dialog` instance without configuration.
     pub fn new() -> Dialog {
         Dialog {
+            program: super::Program::new("dialog"),
             backtitle: None,
             height: "0".to_string(),
             width: "0".to_string(),
@@ -49,19 +59,57 @@ impl Dialog {
         self.width = width.to_string();
     }
 
+    /// Sets the path of the `dialog` executable.
+    ///
+    /// Per default, `dialog` is looked up in the directories listed in the `PATH` environment
+    /// variable.
+    pub fn set_program(&mut self, program: impl Into<path::PathBuf>) {
+        self.program.set_path(program);
+    }
+
+    /// Sets a command prefix that is used to run the `dialog` executable.
+    ///
+    /// The first element is the program to execute, the remaining elements are passed to it as
+    /// arguments, followed by the `dialog` executable and its arguments.  For example, the
+    /// wrapper `["flatpak-spawn", "--host"]` runs `dialog` on the host system from within a
+    /// Flatpak sandbox.
+    ///
+    /// On Unix systems, `dialog` writes its results to the file descriptor 3, so the wrapper must
+    /// pass this file descriptor on to `dialog`.  For `flatpak-spawn`, the `--forward-fd=3`
+    /// option is added automatically.
+    pub fn set_wrapper<I, S>(&mut self, wrapper: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<ffi::OsString>,
+    {
+        self.program.set_wrapper(wrapper);
+    }
+
+    /// Sets an environment variable for the `dialog` process.
+    ///
+    /// This method can be called multiple times to set multiple variables.  If a wrapper is set,
+    /// the variable is passed to `flatpak-spawn` with the `--env` option.  For other wrappers, it
+    /// is set for the wrapper process.
+    pub fn set_env(&mut self, key: impl Into<ffi::OsString>, value: impl Into<ffi::OsString>) {
+        self.program.set_env(key, value);
+    }
+
+    /// Sets the working directory of the `dialog` process.
+    ///
+    /// If a wrapper is set, the directory is passed to `flatpak-spawn` with the `--directory`
+    /// option.  For other wrappers, it is set for the wrapper process.
+    pub fn set_current_dir(&mut self, dir: impl Into<path::PathBuf>) {
+        self.program.set_current_dir(dir);
+    }
+
     pub(crate) fn is_available() -> bool {
         super::is_available("dialog")
     }
 
-    fn execute(
-        &self,
-        args: Vec<&str>,
-        post_args: Vec<&str>,
-        title: &Option<String>,
-    ) -> Result<process::Output> {
-        let mut command = process::Command::new("dialog");
-        command.stdin(process::Stdio::inherit());
-        command.stdout(process::Stdio::inherit());
+    /// Creates a dialog command that passes on the given file descriptors if a wrapper is used.
+    fn command(&self, title: &Option<String>, fds: &[i32]) -> process::Command {
+        let mut command = self.program.command_forwarding(fds);
+        super::attach_terminal(&mut command);
 
         if let Some(ref backtitle) = self.backtitle {
             command.arg("--backtitle");
@@ -71,13 +119,91 @@ impl Dialog {
             command.arg("--title");
             command.arg(title);
         }
+        command
+    }
+
+    fn execute(
+        &self,
+        args: Vec<&str>,
+        post_args: Vec<&str>,
+        title: &Option<String>,
+    ) -> Result<process::Output> {
+        let mut command = self.command(title, FORWARDED_FDS);
+
+        // On non-Unix systems, redirect_output replaces the standard error with the result pipe
+        command.stderr(process::Stdio::piped());
+        let (mut reader, writer) = io::pipe()?;
+        redirect_output(&mut command, &writer);
 
         command.args(args);
         command.arg(&self.height);
         command.arg(&self.width);
         command.args(post_args);
 
-        command.output().map_err(Error::IoError)
+        let child = command.spawn()?;
+        // Close our copy of the write end so that we see EOF once dialog exits
+        drop(writer);
+
+        // The result might contain a password, so make sure that the buffer is not reallocated
+        let mut result = Vec::with_capacity(super::SECRET_CAPACITY);
+        reader.read_to_end(&mut result)?;
+        let output = child.wait_with_output()?;
+        Ok(process::Output {
+            status: output.status,
+            stdout: result,
+            stderr: output.stderr,
+        })
+    }
+}
+
+/// The file descriptor that dialog writes its results to.
+#[cfg(unix)]
+const OUTPUT_FD: i32 = 3;
+
+/// The file descriptors that a wrapper has to pass on to dialog if the result pipe is used.
+#[cfg(unix)]
+const FORWARDED_FDS: &[i32] = &[OUTPUT_FD];
+#[cfg(not(unix))]
+const FORWARDED_FDS: &[i32] = &[];
+
+/// Passes the write end of the result pipe to dialog using `--output-fd`.
+#[cfg(unix)]
+fn redirect_output(command: &mut process::Command, writer: &io::PipeWriter) {
+    use std::os::unix::io::AsRawFd;
+    use std::os::unix::process::CommandExt;
+
+    let fd = writer.as_raw_fd();
+    command.arg("--output-fd");
+    command.arg(OUTPUT_FD.to_string());
+    // SAFETY: dup2 and fcntl are async-signal-safe and only modify the file descriptor table of
+    // the child process.
+    unsafe {
+        command.pre_exec(move || {
+            let result = if fd == OUTPUT_FD {
+                libc::fcntl(fd, libc::F_SETFD, 0)
+            } else {
+                libc::dup2(fd, OUTPUT_FD)
+            };
+            if result == -1 {
+                Err(io::Error::last_os_error())
+            } else {
+                Ok(())
+            }
+        });
+    }
+}
+
+/// Passes the write end of the result pipe to dialog as its standard error.
+#[cfg(not(unix))]
+fn redirect_output(command: &mut process::Command, writer: &io::PipeWriter) {
+    if let Ok(writer) = writer.try_clone() {
+        command.stderr(writer);
+    }
+}
+
+impl Default for Dialog {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -87,6 +213,41 @@ impl AsRef<Dialog> for Dialog {
     }
 }
 
+/// The type of a normal `--mixedform` field.
+const FIELD_NORMAL: u8 = 0;
+/// The type of a hidden `--mixedform` field, for example for passwords.
+const FIELD_HIDDEN: u8 = 1;
+/// The type of a read-only `--mixedform` field.
+const FIELD_READONLY: u8 = 2;
+
+/// The visible width of a `--mixedform` field.
+const FIELD_WIDTH: usize = 30;
+/// The maximum input length of a `--mixedform` field.
+///
+/// dialog limits the input to the field width if the maximum length is zero, so we use a large
+/// limit instead to support long passphrases and tokens.
+const FIELD_MAX_LENGTH: usize = 1024;
+
+/// Returns the `--mixedform` arguments for a field with a label in the given row.
+///
+/// The input field starts at the given column.
+fn form_field(label: &str, row: usize, item: &str, column: usize, itype: u8) -> Vec<String> {
+    let row = row.to_string();
+    let x = "1";
+    let (flen, ilen) = (FIELD_WIDTH.to_string(), FIELD_MAX_LENGTH.to_string());
+    vec![
+        label.to_owned(),
+        row.clone(),
+        x.to_owned(),
+        item.to_owned(),
+        row,
+        column.to_string(),
+        flen,
+        ilen,
+        itype.to_string(),
+    ]
+}
+
 fn require_success(status: process::ExitStatus) -> Result<()> {
     if status.success() {
         Ok(())
@@ -108,9 +269,9 @@ fn get_choice(status: process::ExitStatus) -> Result<Choice> {
     }
 }
 
-fn get_stderr(output: process::Output) -> Result<Option<String>> {
+fn get_output(output: process::Output) -> Result<Option<String>> {
     if output.status.success() {
-        String::from_utf8(output.stderr)
+        String::from_utf8(output.stdout)
             .map(Some)
             .map_err(Error::from)
     } else if let Some(code) = output.status.code() {
@@ -125,7 +286,52 @@ fn get_stderr(output: process::Output) -> Result<Option<String>> {
     }
 }
 
+fn get_secret(mut output: process::Output) -> Result<Option<Secret>> {
+    let secret = super::take_secret(&mut output)?;
+    if output.status.success() {
+        Ok(Some(secret))
+    } else if let Some(code) = output.status.code() {
+        match code {
+            1 => Ok(None),
+            255 => Ok(None),
+            _ => Err(Error::from(("dialog", output.status))),
+        }
+    } else {
+        Err(Error::from(("dialog", output.status)))
+    }
+}
+
 impl super::Backend for Dialog {
+    fn show_form(&self, form: &Form) -> Result<Option<Vec<String>>> {
+        let labels: Vec<String> = form
+            .fields
+            .iter()
+            .map(|field| format!("{}:", field.label))
+            .collect();
+        let column = labels
+            .iter()
+            .map(|label| label.chars().count())
+            .max()
+            .unwrap_or_default()
+            + 2;
+        let args = vec!["--mixedform", &form.text];
+        let mut fields = vec![form.fields.len().to_string()];
+        for (i, (field, label)) in form.fields.iter().zip(&labels).enumerate() {
+            // Never pass the value of a hidden field on the command line
+            let (itype, item) = match field.kind {
+                FieldKind::Text => (FIELD_NORMAL, field.value.as_str()),
+                FieldKind::Password => (FIELD_HIDDEN, ""),
+                FieldKind::ReadOnly => (FIELD_READONLY, field.value.as_str()),
+            };
+            fields.extend(form_field(label, i + 1, item, column, itype));
+        }
+        let post_args = fields.iter().map(String::as_str).collect();
+        let output = self.execute(args, post_args, &form.title)?;
+        get_output(output)?
+            .map(|output| form.merge_values(output.lines().map(ToOwned::to_owned).collect()))
+            .transpose()
+    }
+
     fn show_input(&self, input: &Input) -> Result<Option<String>> {
         let args = vec!["--inputbox", &input.text];
         let mut post_args: Vec<&str> = Vec::new();
@@ -133,7 +339,59 @@ impl super::Backend for Dialog {
             post_args.push(default);
         }
         self.execute(args, post_args, &input.title)
-            .and_then(get_stderr)
+            .and_then(get_output)
+    }
+
+    fn show_license(&self, license: &License) -> Result<bool> {
+        let file = super::TextFile::new(&license.source)?;
+        let path = file.path().to_string_lossy();
+        let args = vec!["--textbox", &path];
+        let output = self.execute(args, vec![], &license.title)?;
+        if get_choice(output.status)? == Choice::Cancel {
+            return Ok(false);
+        }
+        let args = vec!["--defaultno", "--yesno", &license.label];
+        self.execute(args, vec![], &license.title)
+            .and_then(|output| get_choice(output.status))
+            .map(|choice| choice == Choice::Yes)
+    }
+
+    fn show_login(&self, login: &Login) -> Result<Option<Credentials>> {
+        let username_label = format!("{}:", login.username_label);
+        let password_label = format!("{}:", login.password_label);
+        let column = username_label
+            .chars()
+            .count()
+            .max(password_label.chars().count())
+            + 2;
+        let username = login.username.as_deref().unwrap_or("");
+        let args = vec!["--mixedform", &login.text];
+        let mut fields = vec!["2".to_string()];
+        fields.extend(form_field(
+            &username_label,
+            1,
+            username,
+            column,
+            FIELD_NORMAL,
+        ));
+        fields.extend(form_field(&password_label, 2, "", column, FIELD_HIDDEN));
+        let post_args = fields.iter().map(String::as_str).collect();
+        let output = self.execute(args, post_args, &login.title)?;
+        Ok(get_secret(output)?.map(|secret| super::split_credentials(secret, '\n')))
+    }
+
+    fn show_menu(&self, menu: &Menu) -> Result<Option<usize>> {
+        let args = vec!["--menu", &menu.text];
+        let items = menu.tagged_items();
+        let mut post_args = vec!["0"];
+        for (tag, label) in &items {
+            post_args.push(tag);
+            post_args.push(label);
+        }
+        let output = self.execute(args, post_args, &menu.title)?;
+        get_output(output)?
+            .map(|tag| menu.parse_tag(&tag))
+            .transpose()
     }
 
     fn show_message(&self, message: &Message) -> Result<()> {
@@ -143,15 +401,69 @@ impl super::Backend for Dialog {
             .map(|_| ())
     }
 
-    fn show_password(&self, password: &Password) -> Result<Option<String>> {
+    fn show_password(&self, password: &Password) -> Result<Option<Secret>> {
         let args = vec!["--passwordbox", &password.text];
         self.execute(args, vec![], &password.title)
-            .and_then(get_stderr)
+            .and_then(get_secret)
+    }
+
+    fn show_program_box(&self, program: &ProgramBox) -> Result<process::ExitStatus> {
+        let (mut child, reader) = program.spawn()?;
+        let mut command = self.command(&program.title, &[]);
+        command.arg("--programbox");
+        if let Some(ref text) = program.text {
+            command.arg(text);
+        }
+        command.args([&self.height, &self.width]);
+        command.stdin(reader);
+        let status = command.status();
+        // The command keeps the read end of the pipe open, so the program would block forever on
+        // a full pipe if dialog exits early
+        drop(command);
+        // Wait for the program even if dialog failed so that it does not become a zombie
+        let program_status = child.wait()?;
+        require_success(status?)?;
+        Ok(program_status)
     }
 
     fn show_question(&self, question: &Question) -> Result<Choice> {
-        let args = vec!["--yesno", &question.text];
+        let mut args = Vec::new();
+        if question.default == Some(Choice::No) {
+            args.push("--defaultno");
+        }
+        args.push("--yesno");
+        args.push(&question.text);
         self.execute(args, vec![], &question.title)
             .and_then(|output| get_choice(output.status))
     }
+
+    fn show_tail(&self, tail: &Tail) -> Result<()> {
+        tail.wait_for_file();
+        let path = tail.path.to_string_lossy();
+        let mut command = self.command(&tail.title, &[]);
+        command.args(["--tailbox", &path, &self.height, &self.width]);
+        let mut child = command.spawn()?;
+        match super::wait_for_tail(&mut child, tail, || Ok(()))? {
+            Some(status) => get_choice(status).map(|_| ()),
+            None => Ok(()),
+        }
+    }
+
+    fn show_text_input(&self, input: &TextInput) -> Result<Option<String>> {
+        let file = super::TempFile::new(input.default.as_deref().unwrap_or(""))?;
+        let path = file.path().to_string_lossy();
+        let args = vec!["--editbox", &path];
+        self.execute(args, vec![], &input.title)
+            .and_then(get_output)
+            .map(|text| text.map(super::trim_text))
+    }
+
+    fn show_text_viewer(&self, viewer: &TextViewer) -> Result<()> {
+        let file = super::TextFile::new(&viewer.source)?;
+        let path = file.path().to_string_lossy();
+        let args = vec!["--textbox", &path];
+        self.execute(args, vec![], &viewer.title)
+            .and_then(|output| get_choice(output.status))
+            .map(|_| ())
+    }
 }
@@ -0,0 +1,228 @@
+// Copyright (C) 2019 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: MIT
+
+use std::env;
+use std::path;
+use std::process;
+
+use crate::backends::{Backend, Stdio};
+use crate::{
+    Choice, Credentials, Error, Form, Input, License, Login, Menu, Message, Password, ProgramBox,
+    Question, Result, Secret, Tail, TextInput, TextViewer,
+};
+
+/// The prefix of the comment lines in the edited file.
+const COMMENT: char = '#';
+
+/// The `editor` backend.
+///
+/// This backend opens the text editor of the user on a temporary file to query the input for
+/// input and text input dialogs, similar to `git commit`.  The file contains the default value
+/// followed by the title and the text of the dialog as comment lines starting with `#`.  After the
+/// editor has been closed, the comment lines and leading and trailing empty lines are removed.  If
+/// the remaining content is empty, the dialog is cancelled.  For input dialogs, only the first
+/// non-empty line is returned.  If the standard input or output of this process is not a
+/// terminal, the editor is started on the controlling terminal.
+///
+/// The editor is read from the `VISUAL` and `EDITOR` environment variables and defaults to `vi`.
+/// It can be changed using [`set_command`][].  All other dialogs are displayed using a [`Stdio`][]
+/// backend.
+///
+/// # Example
+///
+/// ```no_run
+/// use dialog::DialogBox;
+///
+/// let backend = dialog::backends::Editor::new();
+/// let message = dialog::TextInput::new("Please enter the commit message")
+///     .show_with(&backend)
+///     .expect("Could not display dialog box");
+/// ```
+///
+/// [`set_command`]: #method.set_command
+/// [`Stdio`]: struct.Stdio.html
+#[derive(Debug)]
+pub struct Editor {
+    command: Option<String>,
+    stdio: Stdio,
+}
+
+impl Editor {
+    /// Creates a new `Editor` instance that uses a default [`Stdio`][] instance for dialogs that
+    /// are not edited in the text editor.
+    ///
+    /// [`Stdio`]: struct.Stdio.html
+    pub fn new() -> Editor {
+        Editor::with_stdio(Stdio::new())
+    }
+
+    /// Creates a new `Editor` instance that uses the given [`Stdio`][] instance for dialogs that
+    /// are not edited in the text editor.
+    ///
+    /// [`Stdio`]: struct.Stdio.html
+    pub fn with_stdio(stdio: Stdio) -> Editor {
+        Editor {
+            command: None,
+            stdio,
+        }
+    }
+
+    /// Sets the command used to start the text editor.
+    ///
+    /// The command is interpreted by the shell, so it may contain arguments, for example
+    /// `code --wait`.  The path of the file to edit is appended to the command.  Per default, the
+    /// `VISUAL` and `EDITOR` environment variables are used.
+    pub fn set_command(&mut self, command: impl Into<String>) {
+        self.command = Some(command.into());
+    }
+
+    /// Returns the path of the text editor if it is available.
+    pub(crate) fn program() -> Option<path::PathBuf> {
+        let command = default_command();
+        let program = command.split_whitespace().next()?;
+        if program.contains(path::MAIN_SEPARATOR) {
+            Some(path::PathBuf::from(program)).filter(|path| super::is_executable(path))
+        } else {
+            super::find_executable(program)
+        }
+    }
+
+    pub(crate) fn is_available() -> bool {
+        Editor::program().is_some()
+    }
+
+    fn command(&self) -> String {
+        self.command.clone().unwrap_or_else(default_command)
+    }
+
+    /// Opens the text editor with the given content and the given comment.
+    ///
+    /// Returns `None` if the content without the comment lines is empty.
+    fn edit(&self, title: &Option<String>, text: &str, content: &str) -> Result<Option<String>> {
+        let mut template = content.to_string();
+        if !template.is_empty() && !template.ends_with('\n') {
+            template.push('\n');
+        }
+        template.push('\n');
+        if let Some(ref title) = title {
+            push_comment(&mut template, title);
+            push_comment(&mut template, "");
+        }
+        for line in text.lines() {
+            push_comment(&mut template, line);
+        }
+        push_comment(&mut template, "");
+        push_comment(
+            &mut template,
+            "Lines starting with '#' are ignored.  An empty text cancels the input.",
+        );
+
+        let file = super::TempFile::new(&template)?;
+        let mut command = super::shell_command(&self.command(), &[file.path().as_os_str()]);
+        super::attach_terminal(&mut command);
+        let status = command.status()?;
+        if !status.success() {
+            return Err(Error::from(("editor", status)));
+        }
+
+        let content = file.read()?;
+        let lines: Vec<&str> = content
+            .lines()
+            .filter(|line| !line.starts_with(COMMENT))
+            .collect();
+        let text = lines.join("\n");
+        let text = super::trim_text(text.trim_start_matches(['\n', '\r']).to_string());
+        if text.trim().is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(text))
+        }
+    }
+}
+
+fn push_comment(template: &mut String, line: &str) {
+    template.push(COMMENT);
+    if !line.is_empty() {
+        template.push(' ');
+        template.push_str(line);
+    }
+    template.push('\n');
+}
+
+/// Returns the editor command set in the environment, or `vi` if no editor is set.
+fn default_command() -> String {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|name| env::var(name).ok())
+        .find(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string())
+}
+
+impl Default for Editor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AsRef<Editor> for Editor {
+    fn as_ref(&self) -> &Self {
+        self
+    }
+}
+
+impl Backend for Editor {
+    fn show_form(&self, form: &Form) -> Result<Option<Vec<String>>> {
+        self.stdio.show_form(form)
+    }
+
+    fn show_input(&self, input: &Input) -> Result<Option<String>> {
+        let content = input.default.as_deref().unwrap_or("");
+        let text = self.edit(&input.title, &input.text, content)?;
+        Ok(text.and_then(|text| {
+            text.lines()
+                .find(|line| !line.trim().is_empty())
+                .map(ToOwned::to_owned)
+        }))
+    }
+
+    fn show_license(&self, license: &License) -> Result<bool> {
+        self.stdio.show_license(license)
+    }
+
+    fn show_login(&self, login: &Login) -> Result<Option<Credentials>> {
+        self.stdio.show_login(login)
+    }
+
+    fn show_menu(&self, menu: &Menu) -> Result<Option<usize>> {
+        self.stdio.show_menu(menu)
+    }
+
+    fn show_message(&self, message: &Message) -> Result<()> {
+        self.stdio.show_message(message)
+    }
+
+    fn show_password(&self, password: &Password) -> Result<Option<Secret>> {
+        self.stdio.show_password(password)
+    }
+
+    fn show_program_box(&self, program: &ProgramBox) -> Result<process::ExitStatus> {
+        self.stdio.show_program_box(program)
+    }
+
+    fn show_question(&self, question: &Question) -> Result<Choice> {
+        self.stdio.show_question(question)
+    }
+
+    fn show_tail(&self, tail: &Tail) -> Result<()> {
+        self.stdio.show_tail(tail)
+    }
+
+    fn show_text_input(&self, input: &TextInput) -> Result<Option<String>> {
+        let content = input.default.as_deref().unwrap_or("");
+        self.edit(&input.title, &input.text, content)
+    }
+
+    fn show_text_viewer(&self, viewer: &TextViewer) -> Result<()> {
+        self.stdio.show_text_viewer(viewer)
+    }
+}
@@ -2,9 +2,15 @@
 // Copyright (C) 2019 Stephan Sokolow <http://www.ssokolow.com/ContactMe>
 // SPDX-License-Identifier: MIT
 
+use std::ffi;
+use std::io::Read;
+use std::path;
 use std::process;
 
-use crate::{Choice, Error, Input, Message, Password, Question, Result};
+use crate::{
+    Choice, Credentials, Error, Input, License, Login, Menu, Message, Password, ProgramBox,
+    Question, Result, Secret, Tail, TextInput, TextViewer,
+};
 
 /// Subprocess exit codes
 ///
@@ -24,6 +30,7 @@ const CANCEL: i32 = 1;
 /// This backend uses the external `kdialog` program to display KDE dialog boxes.
 #[derive(Debug)]
 pub struct KDialog {
+    program: super::Program,
     icon: Option<String>,
     // TODO: --dontagain
 }
@@ -32,6 +39,7 @@ impl KDialog {
     /// Creates a new `KDialog` instance without configuration.
     pub fn new() -> KDialog {
         KDialog {
+            program: super::Program::new("kdialog"),
             icon: None,
         }
     }
@@ -46,12 +54,55 @@ impl KDialog {
         self.icon = Some(icon.into());
     }
 
+    /// Sets the path of the `kdialog` executable.
+    ///
+    /// Per default, `kdialog` is looked up in the directories listed in the `PATH` environment
+    /// variable.
+    pub fn set_program(&mut self, program: impl Into<path::PathBuf>) {
+        self.program.set_path(program);
+    }
+
+    /// Sets a command prefix that is used to run the `kdialog` executable.
+    ///
+    /// The first element is the program to execute, the remaining elements are passed to it as
+    /// arguments, followed by the `kdialog` executable and its arguments.  For example, the
+    /// wrapper `["flatpak-spawn", "--host"]` runs `kdialog` on the host system from within a
+    /// Flatpak sandbox.
+    pub fn set_wrapper<I, S>(&mut self, wrapper: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<ffi::OsString>,
+    {
+        self.program.set_wrapper(wrapper);
+    }
+
+    /// Sets an environment variable for the `kdialog` process.
+    ///
+    /// This method can be called multiple times to set multiple variables.  If a wrapper is set,
+    /// the variable is passed to `flatpak-spawn` with the `--env` option.  For other wrappers, it
+    /// is set for the wrapper process.
+    pub fn set_env(&mut self, key: impl Into<ffi::OsString>, value: impl Into<ffi::OsString>) {
+        self.program.set_env(key, value);
+    }
+
+    /// Sets the working directory of the `kdialog` process.
+    ///
+    /// If a wrapper is set, the directory is passed to `flatpak-spawn` with the `--directory`
+    /// option.  For other wrappers, it is set for the wrapper process.
+    pub fn set_current_dir(&mut self, dir: impl Into<path::PathBuf>) {
+        self.program.set_current_dir(dir);
+    }
+
     pub(crate) fn is_available() -> bool {
         super::is_available("kdialog")
     }
 
     fn execute(&self, args: Vec<&str>, title: &Option<String>) -> Result<process::Output> {
-        let mut command = process::Command::new("kdialog");
+        self.command(args, title).output().map_err(Error::IoError)
+    }
+
+    fn command(&self, args: Vec<&str>, title: &Option<String>) -> process::Command {
+        let mut command = self.program.command();
 
         if let Some(ref icon) = self.icon {
             command.arg("--icon");
@@ -63,7 +114,13 @@ impl KDialog {
         }
 
         command.args(args);
-        command.output().map_err(Error::IoError)
+        command
+    }
+}
+
+impl Default for KDialog {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -114,6 +171,20 @@ fn get_stdout(output: process::Output) -> Result<Option<String>> {
     }
 }
 
+fn get_secret(mut output: process::Output) -> Result<Option<Secret>> {
+    let secret = super::take_secret(&mut output)?;
+    if output.status.success() {
+        Ok(Some(secret))
+    } else if let Some(code) = output.status.code() {
+        match code {
+            CANCEL => Ok(None),
+            _ => Err(Error::from(("kdialog", output.status))),
+        }
+    } else {
+        Err(Error::from(("kdialog", output.status)))
+    }
+}
+
 impl super::Backend for KDialog {
     fn show_input(&self, input: &Input) -> Result<Option<String>> {
         let mut args = vec!["--inputbox", &input.text];
@@ -123,6 +194,49 @@ impl super::Backend for KDialog {
         self.execute(args, &input.title).and_then(get_stdout)
     }
 
+    fn show_license(&self, license: &License) -> Result<bool> {
+        let file = super::TextFile::new(&license.source)?;
+        let path = file.path().to_string_lossy();
+        let args = vec!["--textbox", &path];
+        self.execute(args, &license.title)
+            .and_then(|output| require_success(output.status))?;
+        let args = vec!["--yesno", &license.label];
+        self.execute(args, &license.title)
+            .and_then(|output| get_choice(output.status))
+            .map(|choice| choice == Choice::Yes)
+    }
+
+    fn show_login(&self, login: &Login) -> Result<Option<Credentials>> {
+        let text = format!("{}\n\n{}:", login.text, login.username_label);
+        let mut args = vec!["--inputbox", &text];
+        if let Some(ref username) = login.username {
+            args.push(username);
+        }
+        let username = match self.execute(args, &login.title).and_then(get_stdout)? {
+            Some(username) => username,
+            None => return Ok(None),
+        };
+
+        let label = format!("{}:", login.password_label);
+        let args = vec!["--password", &label];
+        let password =
+            super::capture_secret(self.command(args, &login.title)).and_then(get_secret)?;
+        Ok(password.map(|password| Credentials::new(username, password)))
+    }
+
+    fn show_menu(&self, menu: &Menu) -> Result<Option<usize>> {
+        let items = menu.tagged_items();
+        let mut args = vec!["--menu", &menu.text];
+        for (tag, label) in &items {
+            args.push(tag);
+            args.push(label);
+        }
+        self.execute(args, &menu.title)
+            .and_then(get_stdout)?
+            .map(|tag| menu.parse_tag(&tag))
+            .transpose()
+    }
+
     fn show_message(&self, message: &Message) -> Result<()> {
         let args = vec!["--msgbox", &message.text];
         self.execute(args, &message.title)
@@ -130,9 +244,30 @@ impl super::Backend for KDialog {
             .map(|_| ())
     }
 
-    fn show_password(&self, password: &Password) -> Result<Option<String>> {
+    fn show_password(&self, password: &Password) -> Result<Option<Secret>> {
         let args = vec!["--password", &password.text];
-        self.execute(args, &password.title).and_then(get_stdout)
+        super::capture_secret(self.command(args, &password.title)).and_then(get_secret)
+    }
+
+    fn show_program_box(&self, program: &ProgramBox) -> Result<process::ExitStatus> {
+        let (mut child, mut reader) = program.spawn()?;
+        let mut output = match program.text {
+            Some(ref text) => format!("{}\n\n", text),
+            None => String::new(),
+        };
+        // The output of the program is not necessarily valid UTF-8
+        let mut buffer = Vec::new();
+        let result = reader.read_to_end(&mut buffer);
+        let status = child.wait()?;
+        result?;
+        output.push_str(&String::from_utf8_lossy(&buffer));
+
+        let file = super::TempFile::new(&output)?;
+        let path = file.path().to_string_lossy();
+        let args = vec!["--textbox", &path];
+        self.execute(args, &program.title)
+            .and_then(|output| get_choice(output.status))?;
+        Ok(status)
     }
 
     fn show_question(&self, question: &Question) -> Result<Choice> {
@@ -140,4 +275,35 @@ impl super::Backend for KDialog {
         self.execute(args, &question.title)
             .and_then(|output| get_choice(output.status))
     }
+
+    fn show_tail(&self, tail: &Tail) -> Result<()> {
+        tail.wait_for_file();
+        // kdialog cannot follow a file, so only show the current content
+        let path = tail.path.to_string_lossy();
+        let args = vec!["--textbox", &path];
+        let mut child = self.command(args, &tail.title).spawn()?;
+        match super::wait_for_tail(&mut child, tail, || Ok(()))? {
+            Some(status) => get_choice(status).map(|_| ()),
+            None => Ok(()),
+        }
+    }
+
+    fn show_text_input(&self, input: &TextInput) -> Result<Option<String>> {
+        let mut args = vec!["--textinputbox", &input.text];
+        if let Some(ref default) = input.default {
+            args.push(default);
+        }
+        self.execute(args, &input.title)
+            .and_then(get_stdout)
+            .map(|text| text.map(super::trim_text))
+    }
+
+    fn show_text_viewer(&self, viewer: &TextViewer) -> Result<()> {
+        let file = super::TextFile::new(&viewer.source)?;
+        let path = file.path().to_string_lossy();
+        let args = vec!["--textbox", &path];
+        self.execute(args, &viewer.title)
+            .and_then(|output| get_choice(output.status))
+            .map(|_| ())
+    }
 }
@@ -2,19 +2,31 @@
 // SPDX-License-Identifier: MIT
 
 mod dialog;
+mod editor;
 mod stdio;
 mod zenity;
 mod kdialog;
 
 pub use crate::backends::dialog::Dialog;
-pub use crate::backends::stdio::Stdio;
-pub use crate::backends::zenity::Zenity;
+pub use crate::backends::editor::Editor;
+pub use crate::backends::stdio::{Catalog, PasswordFallback, Stdio};
+pub use crate::backends::zenity::{Zenity, ZenityVersion};
 pub use crate::backends::kdialog::KDialog;
 
+use std::collections::HashMap;
 use std::env;
+use std::ffi;
+use std::fs;
+use std::io::{self, IsTerminal, Read, Write};
+use std::mem;
 use std::path;
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time;
 
-use crate::Result;
+use crate::{Credentials, Error, Result, Secret, Tail, TextSource};
 
 /// A dialog backend.
 ///
@@ -23,39 +35,770 @@ use crate::Result;
 /// backend and create an instance manually.  To use a backend, pass it to the [`show_with`][]
 /// method of a dialog box.
 ///
+/// To implement a custom backend, only the methods for input, message, password and question
+/// dialogs are required.  The other methods have default implementations that use these dialogs.
+///
 /// [`default_backend`]: ../fn.default_backend.html
 /// [`show_with`]: ../trait.DialogBox.html#method.show_with
 pub trait Backend {
+    /// Shows the given form dialog and returns the values of the fields.
+    ///
+    /// The default implementation asks for the values of the fields one after another using
+    /// [`show_input`][] and [`show_password`][].
+    ///
+    /// [`show_input`]: #tymethod.show_input
+    /// [`show_password`]: #tymethod.show_password
+    fn show_form(&self, form: &super::Form) -> Result<Option<Vec<String>>> {
+        let mut text = form.text.clone();
+        for field in form.fields.iter().filter(|field| !field.is_editable()) {
+            text.push_str(&format!("\n{}: {}", field.label, field.value));
+        }
+        let mut values = Vec::with_capacity(form.fields.len());
+        for field in &form.fields {
+            let prompt = format!("{}\n\n{}:", text, field.label);
+            let value = match field.kind {
+                super::FieldKind::Text => {
+                    let mut input = super::Input::new(prompt);
+                    input.title = form.title.clone();
+                    if !field.value.is_empty() {
+                        input.default(field.value.as_str());
+                    }
+                    self.show_input(&input)?
+                }
+                super::FieldKind::Password => {
+                    let mut password = super::Password::new(prompt);
+                    password.title = form.title.clone();
+                    self.show_password(&password)?
+                        .map(|secret| secret.expose().to_owned())
+                }
+                super::FieldKind::ReadOnly => Some(field.value.clone()),
+            };
+            match value {
+                Some(value) => values.push(value),
+                None => return Ok(None),
+            }
+        }
+        Ok(Some(values))
+    }
+
     /// Shows the given input dialog and returns the input.
     fn show_input(&self, input: &super::Input) -> Result<Option<String>>;
 
+    /// Shows the given license dialog and returns whether the license was accepted.
+    ///
+    /// The default implementation shows the license and the label in a question dialog using
+    /// [`show_question`][].
+    ///
+    /// [`show_question`]: #tymethod.show_question
+    fn show_license(&self, license: &super::License) -> Result<bool> {
+        let mut question =
+            super::Question::new(format!("{}\n\n{}", license.source.read()?, license.label));
+        question.title = license.title.clone();
+        question.default(super::Choice::No);
+        Ok(self.show_question(&question)? == super::Choice::Yes)
+    }
+
+    /// Shows the given login dialog and returns the credentials.
+    ///
+    /// The default implementation asks for the username using [`show_input`][] and for the
+    /// password using [`show_password`][].
+    ///
+    /// [`show_input`]: #tymethod.show_input
+    /// [`show_password`]: #tymethod.show_password
+    fn show_login(&self, login: &super::Login) -> Result<Option<super::Credentials>> {
+        let mut input = super::Input::new(format!("{}\n\n{}:", login.text, login.username_label));
+        input.title = login.title.clone();
+        input.default = login.username.clone();
+        let username = match self.show_input(&input)? {
+            Some(username) => username,
+            None => return Ok(None),
+        };
+        let mut password = super::Password::new(format!("{}:", login.password_label));
+        password.title = login.title.clone();
+        let password = self.show_password(&password)?;
+        Ok(password.map(|password| Credentials::new(username, password)))
+    }
+
+    /// Shows the given menu dialog and returns the index of the selected item.
+    ///
+    /// The default implementation shows a numbered list of the items and asks for the number of
+    /// the selected item using [`show_input`][] until a valid number is entered.
+    ///
+    /// [`show_input`]: #tymethod.show_input
+    fn show_menu(&self, menu: &super::Menu) -> Result<Option<usize>> {
+        let mut text = format!("{}\n", menu.text);
+        for (tag, label) in menu.tagged_items() {
+            text.push_str(&format!("\n{}) {}", tag, label));
+        }
+        let mut input = super::Input::new(text);
+        input.title = menu.title.clone();
+        // Ask again until the input is the number of an item
+        input.show_checked(self, |tag| {
+            menu.parse_tag(tag)
+                .map_err(|_| format!("Please enter a number from 1 to {}.", menu.items.len()))
+        })
+    }
+
     /// Shows the given message dialog.
     fn show_message(&self, message: &super::Message) -> Result<()>;
 
     /// Shows the given password dialog and returns the password.
-    fn show_password(&self, password: &super::Password) -> Result<Option<String>>;
+    fn show_password(&self, password: &super::Password) -> Result<Option<Secret>>;
+
+    /// Runs the command of the given program box, shows its output and returns its exit status.
+    ///
+    /// The default implementation waits until the command has finished and then shows its output
+    /// using [`show_message`][].
+    ///
+    /// [`show_message`]: #tymethod.show_message
+    fn show_program_box(&self, program: &super::ProgramBox) -> Result<process::ExitStatus> {
+        let (mut child, mut reader) = program.spawn()?;
+        let mut output = Vec::new();
+        let result = reader.read_to_end(&mut output);
+        let status = child.wait()?;
+        result?;
+        let output = String::from_utf8_lossy(&output);
+        let mut message = super::Message::new(match program.text {
+            Some(ref text) => format!("{}\n\n{}", text, output),
+            None => output.into_owned(),
+        });
+        message.title = program.title.clone();
+        self.show_message(&message)?;
+        Ok(status)
+    }
 
     /// Shows the given question dialog and returns the choice.
     fn show_question(&self, question: &super::Question) -> Result<super::Choice>;
+
+    /// Shows the given tail dialog until it is closed or completed.
+    ///
+    /// The default implementation shows the current content of the file using
+    /// [`show_message`][].
+    ///
+    /// [`show_message`]: #tymethod.show_message
+    fn show_tail(&self, tail: &super::Tail) -> Result<()> {
+        tail.wait_for_file();
+        let content = fs::read(&tail.path)?;
+        let mut message = super::Message::new(String::from_utf8_lossy(&content));
+        message.title = tail.title.clone();
+        self.show_message(&message)
+    }
+
+    /// Shows the given text input dialog and returns the text.
+    ///
+    /// The default implementation asks for a single line using [`show_input`][].
+    ///
+    /// [`show_input`]: #tymethod.show_input
+    fn show_text_input(&self, input: &super::TextInput) -> Result<Option<String>> {
+        let mut line = super::Input::new(input.text.as_str());
+        line.title = input.title.clone();
+        line.default = input.default.clone();
+        self.show_input(&line)
+    }
+
+    /// Shows the given text viewer.
+    ///
+    /// The default implementation shows the text using [`show_message`][].
+    ///
+    /// [`show_message`]: #tymethod.show_message
+    fn show_text_viewer(&self, viewer: &super::TextViewer) -> Result<()> {
+        let mut message = super::Message::new(viewer.source.read()?);
+        message.title = viewer.title.clone();
+        self.show_message(&message)
+    }
 }
 
-pub(crate) fn is_available(name: &str) -> bool {
-    if let Ok(path) = env::var("PATH") {
-        for part in path.split(':') {
-            if path::Path::new(part).join(name).exists() {
-                return true;
+/// A backend that is available on this system.
+///
+/// Instances of this struct are returned by [`available_backends`][].
+///
+/// [`available_backends`]: fn.available_backends.html
+#[derive(Clone, Debug, PartialEq)]
+pub struct AvailableBackend {
+    name: &'static str,
+    path: Option<path::PathBuf>,
+}
+
+impl AvailableBackend {
+    /// Returns the name of the backend as accepted by the `DIALOG` environment variable.
+    pub fn name(&self) -> &str {
+        self.name
+    }
+
+    /// Returns the resolved path of the executable used by the backend.
+    ///
+    /// This is `None` for backends that do not require an external program, for example
+    /// [`Stdio`][].
+    ///
+    /// [`Stdio`]: struct.Stdio.html
+    pub fn path(&self) -> Option<&path::Path> {
+        self.path.as_deref()
+    }
+}
+
+/// Returns the list of backends that are available on this system.
+///
+/// A backend that requires an external program is available if the program is found as an
+/// executable file in one of the directories listed in the `PATH` environment variable.  The
+/// lookup results are cached for the lifetime of the process.  The [`Stdio`][] backend is always
+/// available and listed last.
+///
+/// # Example
+///
+/// ```
+/// for backend in dialog::backends::available_backends() {
+///     match backend.path() {
+///         Some(path) => println!("{}: {}", backend.name(), path.display()),
+///         None => println!("{}", backend.name()),
+///     }
+/// }
+/// ```
+///
+/// [`Stdio`]: struct.Stdio.html
+pub fn available_backends() -> Vec<AvailableBackend> {
+    let mut backends: Vec<AvailableBackend> = ["dialog", "kdialog"]
+        .iter()
+        .chain(zenity::PROGRAMS.iter())
+        .filter_map(|name| {
+            find_executable(name).map(|path| AvailableBackend {
+                name,
+                path: Some(path),
+            })
+        })
+        .collect();
+    if let Some(path) = Editor::program() {
+        backends.push(AvailableBackend {
+            name: "editor",
+            path: Some(path),
+        });
+    }
+    backends.push(AvailableBackend {
+        name: "stdio",
+        path: None,
+    });
+    backends
+}
+
+/// The external program used by a backend and the environment it is executed in.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub(crate) struct Program {
+    path: path::PathBuf,
+    wrapper: Vec<ffi::OsString>,
+    envs: Vec<(ffi::OsString, ffi::OsString)>,
+    current_dir: Option<path::PathBuf>,
+}
+
+impl Program {
+    pub(crate) fn new(path: impl Into<path::PathBuf>) -> Program {
+        Program {
+            path: path.into(),
+            wrapper: Vec::new(),
+            envs: Vec::new(),
+            current_dir: None,
+        }
+    }
+
+    pub(crate) fn path(&self) -> &path::Path {
+        &self.path
+    }
+
+    pub(crate) fn set_path(&mut self, path: impl Into<path::PathBuf>) {
+        self.path = path.into();
+    }
+
+    pub(crate) fn set_wrapper<I, S>(&mut self, wrapper: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<ffi::OsString>,
+    {
+        self.wrapper = wrapper.into_iter().map(Into::into).collect();
+    }
+
+    pub(crate) fn set_env(
+        &mut self,
+        key: impl Into<ffi::OsString>,
+        value: impl Into<ffi::OsString>,
+    ) {
+        self.envs.push((key.into(), value.into()));
+    }
+
+    pub(crate) fn set_current_dir(&mut self, dir: impl Into<path::PathBuf>) {
+        self.current_dir = Some(dir.into());
+    }
+
+    /// Creates a command that executes this program, prefixed with the wrapper if set.
+    pub(crate) fn command(&self) -> process::Command {
+        self.command_forwarding(&[])
+    }
+
+    /// Creates a command that executes this program, prefixed with the wrapper if set, and makes
+    /// sure that the given file descriptors are passed on to the program.
+    ///
+    /// `flatpak-spawn` does not pass on its environment, its working directory and file
+    /// descriptors other than the standard streams, so the environment variables, the working
+    /// directory and the file descriptors are passed with the `--env`, `--directory` and
+    /// `--forward-fd` options.  For other wrappers, the environment variables and the working
+    /// directory are set for the wrapper process, and the wrapper is expected to pass on all file
+    /// descriptors.
+    pub(crate) fn command_forwarding(&self, fds: &[i32]) -> process::Command {
+        let (wrapper, args) = match self.wrapper.split_first() {
+            Some(wrapper) => wrapper,
+            None => {
+                let mut command = process::Command::new(&self.path);
+                self.set_environment(&mut command);
+                return command;
             }
+        };
+
+        let mut command = process::Command::new(wrapper);
+        command.args(args);
+        if self.is_flatpak_spawn() {
+            for (key, value) in &self.envs {
+                let mut arg = ffi::OsString::from("--env=");
+                arg.push(key);
+                arg.push("=");
+                arg.push(value);
+                command.arg(arg);
+            }
+            if let Some(ref dir) = self.current_dir {
+                let mut arg = ffi::OsString::from("--directory=");
+                arg.push(dir);
+                command.arg(arg);
+            }
+            command.args(fds.iter().map(|fd| format!("--forward-fd={}", fd)));
+        } else {
+            self.set_environment(&mut command);
+        }
+        command.arg(&self.path);
+        command
+    }
+
+    /// Sets the environment variables and the working directory for the given command.
+    fn set_environment(&self, command: &mut process::Command) {
+        command.envs(self.envs.iter().map(|(key, value)| (key, value)));
+        if let Some(ref dir) = self.current_dir {
+            command.current_dir(dir);
+        }
+    }
+
+    /// Returns whether the wrapper is `flatpak-spawn`.
+    fn is_flatpak_spawn(&self) -> bool {
+        self.wrapper
+            .first()
+            .and_then(|wrapper| path::Path::new(wrapper).file_name())
+            .map(|name| name == "flatpak-spawn")
+            .unwrap_or_default()
+    }
+}
+
+/// The initial capacity of the buffers used to capture secrets.
+///
+/// The buffers are allocated with this capacity so that they do not have to be reallocated, which
+/// would leave copies of the secret in memory.
+pub(crate) const SECRET_CAPACITY: usize = 4096;
+
+/// Executes the given command and captures its standard output, which contains a secret.
+///
+/// The standard output is read into a pre-allocated buffer and the standard error is discarded.
+pub(crate) fn capture_secret(mut command: process::Command) -> Result<process::Output> {
+    command.stdout(process::Stdio::piped());
+    command.stderr(process::Stdio::null());
+    let mut child = command.spawn()?;
+    let mut stdout = Vec::with_capacity(SECRET_CAPACITY);
+    if let Some(mut pipe) = child.stdout.take() {
+        if let Err(err) = pipe.read_to_end(&mut stdout) {
+            zeroize::Zeroize::zeroize(&mut stdout);
+            return Err(err.into());
+        }
+    }
+    let status = child.wait()?;
+    Ok(process::Output {
+        status,
+        stdout,
+        stderr: Vec::new(),
+    })
+}
+
+/// Splits a secret that contains a username and a password at the first occurence of the given
+/// separator.
+///
+/// If the secret does not contain the separator, it is treated as a username with an empty
+/// password.
+pub(crate) fn split_credentials(secret: Secret, separator: char) -> Credentials {
+    let s = secret.expose();
+    let (username, password) = s.split_once(separator).unwrap_or((s, ""));
+    Credentials::new(username, Secret::from(password.to_string()))
+}
+
+/// Converts the standard output of the given process output into a secret and wipes the buffers.
+///
+/// A trailing newline is removed from the secret.
+pub(crate) fn take_secret(output: &mut process::Output) -> Result<Secret> {
+    zeroize::Zeroize::zeroize(&mut output.stderr);
+    match String::from_utf8(mem::take(&mut output.stdout)) {
+        Ok(mut s) => {
+            let len = s.trim_end_matches('\n').len();
+            s.truncate(len);
+            Ok(Secret::from(s))
+        }
+        Err(err) => {
+            let utf8_error = err.utf8_error();
+            zeroize::Zeroize::zeroize(&mut err.into_bytes());
+            Err(Error::from(utf8_error))
         }
     }
-    false
+}
+
+/// Removes the trailing line breaks from the result of a text input dialog.
+pub(crate) fn trim_text(mut text: String) -> String {
+    let len = text.trim_end_matches(['\n', '\r']).len();
+    text.truncate(len);
+    text
+}
+
+/// Makes sure that the given interactive program draws on the controlling terminal.
+///
+/// If the standard input or output of this process is not a terminal, for example because the
+/// output is redirected to a file or a pipe, the program is connected to `/dev/tty` instead.  If
+/// there is no controlling terminal, the standard streams are inherited.
+pub(crate) fn attach_terminal(command: &mut process::Command) {
+    if io::stdin().is_terminal() && io::stdout().is_terminal() {
+        return;
+    }
+    let tty = fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tty");
+    if let Ok(tty) = tty {
+        if let Ok(tty_in) = tty.try_clone() {
+            command.stdin(tty_in);
+            command.stdout(tty);
+        }
+    }
+}
+
+/// Returns a command that executes the given command line with the given arguments appended.
+///
+/// On Unix systems, the command line is interpreted by the shell like `git` does for the `EDITOR`
+/// and `PAGER` variables.  On other systems, it is split at whitespace.
+#[cfg(unix)]
+pub(crate) fn shell_command(command: &str, args: &[&ffi::OsStr]) -> process::Command {
+    // The arguments are passed as positional parameters so that they do not have to be quoted
+    let mut shell = process::Command::new("sh");
+    shell.arg("-c");
+    shell.arg(format!("{} \"$@\"", command));
+    shell.arg(command);
+    shell.args(args);
+    shell
+}
+
+#[cfg(not(unix))]
+pub(crate) fn shell_command(command: &str, args: &[&ffi::OsStr]) -> process::Command {
+    let mut parts = command.split_whitespace();
+    let mut program = process::Command::new(parts.next().unwrap_or_default());
+    program.args(parts);
+    program.args(args);
+    program
+}
+
+/// A temporary file that is deleted when it is dropped.
+#[derive(Debug)]
+pub(crate) struct TempFile {
+    path: path::PathBuf,
+}
+
+impl TempFile {
+    /// Creates a new temporary file with the given content that is only accessible by the user.
+    pub(crate) fn new(content: &str) -> Result<TempFile> {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        loop {
+            let name = format!(
+                "dialog-rs-{}-{}",
+                process::id(),
+                COUNTER.fetch_add(1, Ordering::Relaxed)
+            );
+            let path = env::temp_dir().join(name);
+            let mut options = fs::OpenOptions::new();
+            options.write(true).create_new(true);
+            #[cfg(unix)]
+            std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+            match options.open(&path) {
+                Ok(mut file) => {
+                    let temp_file = TempFile { path };
+                    file.write_all(content.as_bytes())?;
+                    return Ok(temp_file);
+                }
+                Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+                Err(err) => return Err(err.into()),
+            }
+        }
+    }
+
+    pub(crate) fn path(&self) -> &path::Path {
+        &self.path
+    }
+
+    /// Reads the content of this file.
+    pub(crate) fn read(&self) -> Result<String> {
+        Ok(fs::read_to_string(&self.path)?)
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// The interval for polling followed files and running processes.
+pub(crate) const POLL_INTERVAL: time::Duration = time::Duration::from_millis(100);
+
+/// Reads the data that is appended to a file.
+#[derive(Debug)]
+pub(crate) struct Follower {
+    file: fs::File,
+}
+
+impl Follower {
+    pub(crate) fn open(path: &path::Path) -> Result<Follower> {
+        Ok(Follower {
+            file: fs::File::open(path)?,
+        })
+    }
+
+    /// Reads the data that has been appended since the last call.
+    pub(crate) fn read_new(&mut self) -> Result<Vec<u8>> {
+        let mut data = Vec::new();
+        self.file.read_to_end(&mut data)?;
+        Ok(data)
+    }
+}
+
+/// Waits for the given child process to exit and terminates it once the tail is complete.
+///
+/// The given function is called regularly while the child is running.  Returns the exit status if
+/// the child exited by itself, or `None` if it was terminated.
+pub(crate) fn wait_for_tail(
+    child: &mut process::Child,
+    tail: &Tail,
+    mut poll: impl FnMut() -> Result<()>,
+) -> Result<Option<process::ExitStatus>> {
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        poll()?;
+        if tail.is_complete() {
+            terminate(child)?;
+            child.wait()?;
+            return Ok(None);
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Asks the given child process to exit so that it can restore the terminal.
+#[cfg(unix)]
+fn terminate(child: &mut process::Child) -> io::Result<()> {
+    // SAFETY: kill does not access any memory of this process.
+    if unsafe { libc::kill(child.id() as libc::pid_t, libc::SIGTERM) } == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+#[cfg(not(unix))]
+fn terminate(child: &mut process::Child) -> io::Result<()> {
+    child.kill()
+}
+
+/// A file that contains the text of a text source, either the original file or a temporary file.
+#[derive(Debug)]
+pub(crate) enum TextFile<'a> {
+    Original(&'a path::Path),
+    Temp(TempFile),
+}
+
+impl TextFile<'_> {
+    /// Returns a file that contains the given text source.
+    pub(crate) fn new(source: &TextSource) -> Result<TextFile<'_>> {
+        match source {
+            TextSource::Text(text) => TempFile::new(text).map(TextFile::Temp),
+            TextSource::File(path) => Ok(TextFile::Original(path)),
+        }
+    }
+
+    pub(crate) fn path(&self) -> &path::Path {
+        match self {
+            TextFile::Original(path) => path,
+            TextFile::Temp(file) => file.path(),
+        }
+    }
+}
+
+pub(crate) fn is_available(name: &str) -> bool {
+    find_executable(name).is_some()
+}
+
+/// Searches the directories listed in the `PATH` environment variable for an executable file
+/// with the given name and caches the result.
+pub(crate) fn find_executable(name: &str) -> Option<path::PathBuf> {
+    static CACHE: OnceLock<Mutex<HashMap<String, Option<path::PathBuf>>>> = OnceLock::new();
+
+    let cache = CACHE.get_or_init(Default::default);
+    let mut cache = cache.lock().unwrap_or_else(|err| err.into_inner());
+    cache
+        .entry(name.to_string())
+        .or_insert_with(|| search_path(name))
+        .clone()
+}
+
+fn search_path(name: &str) -> Option<path::PathBuf> {
+    let paths = env::var_os("PATH")?;
+    search_dirs(&paths, env::current_dir().ok().as_deref(), name)
+}
+
+/// Searches the given list of directories for an executable file with the given name.
+fn search_dirs(
+    paths: &ffi::OsStr,
+    current_dir: Option<&path::Path>,
+    name: &str,
+) -> Option<path::PathBuf> {
+    env::split_paths(paths)
+        .filter_map(|dir| {
+            // An empty entry refers to the current directory, see POSIX.1-2017, section 8.3.  The
+            // path is made absolute so that it stays valid if the working directory changes.
+            if dir.as_os_str().is_empty() {
+                current_dir.map(|current_dir| current_dir.join(name))
+            } else {
+                Some(dir.join(name))
+            }
+        })
+        .find(|path| is_executable(path))
+}
+
+#[cfg(unix)]
+pub(crate) fn is_executable(path: &path::Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    // fs::metadata follows symbolic links, so a link to an executable file is accepted
+    match fs::metadata(path) {
+        Ok(metadata) => metadata.is_file() && metadata.permissions().mode() & 0o111 != 0,
+        Err(_) => false,
+    }
+}
+
+#[cfg(not(unix))]
+pub(crate) fn is_executable(path: &path::Path) -> bool {
+    fs::metadata(path)
+        .map(|metadata| metadata.is_file())
+        .unwrap_or(false)
+}
+
+/// Returns whether the given string is the name of a backend, without creating the backend.
+pub(crate) fn is_known_backend(s: &str) -> bool {
+    matches!(
+        s.to_lowercase().as_ref(),
+        "dialog" | "editor" | "kdialog" | "stdio" | "zenity" | "qarma" | "matedialog"
+    )
+}
+
+pub(crate) fn is_backend_available(s: &str) -> bool {
+    match s.to_lowercase().as_ref() {
+        "dialog" => Dialog::is_available(),
+        "editor" => Editor::is_available(),
+        "kdialog" => KDialog::is_available(),
+        "stdio" => true,
+        "zenity" | "qarma" | "matedialog" => is_available(&s.to_lowercase()),
+        _ => false,
+    }
 }
 
 pub(crate) fn from_str(s: &str) -> Option<Box<dyn Backend>> {
     match s.to_lowercase().as_ref() {
         "dialog" => Some(Box::new(Dialog::new())),
+        "editor" => Some(Box::new(Editor::new())),
         "kdialog" => Some(Box::new(KDialog::new())),
         "stdio" => Some(Box::new(Stdio::new())),
         "zenity" => Some(Box::new(Zenity::new())),
+        "qarma" => Some(Box::new(Zenity::with_program("qarma"))),
+        "matedialog" => Some(Box::new(Zenity::with_program("matedialog"))),
         _ => None,
     }
 }
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::env;
+    use std::ffi;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use std::path;
+    use std::process;
+
+    /// Creates an empty test directory with the given name.
+    fn test_dir(name: &str) -> path::PathBuf {
+        let dir = env::temp_dir().join(format!("dialog-rs-test-{}-{}", process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn create_file(path: &path::Path, mode: u32) {
+        fs::write(path, "").unwrap();
+        fs::set_permissions(path, fs::Permissions::from_mode(mode)).unwrap();
+    }
+
+    fn join_paths(dirs: &[&path::Path]) -> ffi::OsString {
+        env::join_paths(dirs).unwrap()
+    }
+
+    #[test]
+    fn search_dirs_executable() {
+        let dir = test_dir("search-executable");
+        let plain = dir.join("plain");
+        let bin = dir.join("bin");
+        fs::create_dir(&plain).unwrap();
+        fs::create_dir(&bin).unwrap();
+        create_file(&plain.join("tool"), 0o644);
+        create_file(&bin.join("tool"), 0o755);
+
+        let paths = join_paths(&[&plain, &bin]);
+        assert_eq!(
+            Some(bin.join("tool")),
+            super::search_dirs(&paths, None, "tool")
+        );
+        assert_eq!(None, super::search_dirs(&paths, None, "missing"));
+        let paths = join_paths(&[&plain]);
+        assert_eq!(None, super::search_dirs(&paths, None, "tool"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn search_dirs_directory() {
+        let dir = test_dir("search-directory");
+        let sub = dir.join("tool");
+        fs::create_dir(&sub).unwrap();
+        fs::set_permissions(&sub, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let paths = join_paths(&[&dir]);
+        assert_eq!(None, super::search_dirs(&paths, None, "tool"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn search_dirs_empty_entry() {
+        let dir = test_dir("search-empty");
+        create_file(&dir.join("tool"), 0o755);
+
+        let paths = ffi::OsStr::new(":/nonexistent");
+        assert_eq!(
+            Some(dir.join("tool")),
+            super::search_dirs(paths, Some(&dir), "tool")
+        );
+        assert_eq!(None, super::search_dirs(paths, None, "tool"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
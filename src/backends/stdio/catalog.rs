@@ -0,0 +1,402 @@
+// Copyright (C) 2019 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: MIT
+
+use std::env;
+
+use crate::{CharClass, Choice};
+
+/// The localized strings used by the [`Stdio`][] backend.
+///
+/// A catalog contains the strings that are used to display prompts and the answers that are
+/// accepted for questions.  It also contains the error messages of the password policy of a
+/// [`Password`][] dialog box, which are displayed by all backends.  There are built-in catalogs
+/// for English, German and French.  Per default, the catalog is chosen according to the locale
+/// set in the environment, see [`from_env`][].  Custom catalogs can be created by modifying one
+/// of the built-in catalogs.
+///
+/// # Example
+///
+/// ```no_run
+/// use dialog::backends::{Catalog, Stdio};
+/// use dialog::DialogBox;
+///
+/// let mut catalog = Catalog::english();
+/// catalog.set_answers(&["s", "si", "sí"], &["n", "no"]);
+/// catalog.set_default_label("por defecto");
+/// catalog.set_invalid_answer("Por favor, responda sí o no.");
+///
+/// let mut backend = Stdio::new();
+/// backend.set_catalog(catalog);
+/// let choice = dialog::Question::new("¿Continuar?")
+///     .show_with(&backend)
+///     .expect("Could not display dialog box");
+/// ```
+///
+/// [`Stdio`]: struct.Stdio.html
+/// [`Password`]: ../struct.Password.html
+/// [`from_env`]: #method.from_env
+#[derive(Clone, Debug, PartialEq)]
+pub struct Catalog {
+    yes: Vec<String>,
+    no: Vec<String>,
+    default_label: String,
+    invalid_answer: String,
+    text_input_hint: String,
+    more_prompt: String,
+    tail_hint: String,
+    invalid_item: String,
+    password_mismatch: String,
+    password_too_short: String,
+    missing_lowercase: String,
+    missing_uppercase: String,
+    missing_digit: String,
+    missing_symbol: String,
+}
+
+impl Catalog {
+    /// Returns the built-in English catalog.
+    pub fn english() -> Catalog {
+        Catalog {
+            yes: strings(&["y", "yes"]),
+            no: strings(&["n", "no"]),
+            default_label: "default".to_string(),
+            invalid_answer: "Please answer yes or no.".to_string(),
+            text_input_hint: "Finish the text with a line that only contains a period.".to_string(),
+            more_prompt: "-- More -- (Enter: next page, q: quit)".to_string(),
+            tail_hint: "Press Enter to stop following the output.".to_string(),
+            invalid_item: "Please enter the number of an item.".to_string(),
+            password_mismatch: "The passwords do not match.".to_string(),
+            password_too_short: "The password must be at least {} characters long.".to_string(),
+            missing_lowercase: "The password must contain a lowercase letter.".to_string(),
+            missing_uppercase: "The password must contain an uppercase letter.".to_string(),
+            missing_digit: "The password must contain a digit.".to_string(),
+            missing_symbol: "The password must contain a symbol.".to_string(),
+        }
+    }
+
+    /// Returns the built-in German catalog.
+    ///
+    /// The English answers are accepted too.
+    pub fn german() -> Catalog {
+        Catalog {
+            yes: strings(&["j", "ja", "y", "yes"]),
+            no: strings(&["n", "nein", "no"]),
+            default_label: "Standard".to_string(),
+            invalid_answer: "Bitte mit ja oder nein antworten.".to_string(),
+            text_input_hint: "Den Text mit einer Zeile beenden, die nur einen Punkt enthält."
+                .to_string(),
+            more_prompt: "-- Mehr -- (Enter: nächste Seite, q: beenden)".to_string(),
+            tail_hint: "Enter drücken, um die Ausgabe nicht weiter zu verfolgen.".to_string(),
+            invalid_item: "Bitte die Nummer eines Eintrags eingeben.".to_string(),
+            password_mismatch: "Die Passwörter stimmen nicht überein.".to_string(),
+            password_too_short: "Das Passwort muss mindestens {} Zeichen lang sein.".to_string(),
+            missing_lowercase: "Das Passwort muss einen Kleinbuchstaben enthalten.".to_string(),
+            missing_uppercase: "Das Passwort muss einen Großbuchstaben enthalten.".to_string(),
+            missing_digit: "Das Passwort muss eine Ziffer enthalten.".to_string(),
+            missing_symbol: "Das Passwort muss ein Sonderzeichen enthalten.".to_string(),
+        }
+    }
+
+    /// Returns the built-in French catalog.
+    ///
+    /// The English answers are accepted too.
+    pub fn french() -> Catalog {
+        Catalog {
+            yes: strings(&["o", "oui", "y", "yes"]),
+            no: strings(&["n", "non", "no"]),
+            default_label: "défaut".to_string(),
+            invalid_answer: "Veuillez répondre par oui ou non.".to_string(),
+            text_input_hint: "Terminez le texte par une ligne qui ne contient qu'un point."
+                .to_string(),
+            more_prompt: "-- Suite -- (Entrée : page suivante, q : quitter)".to_string(),
+            tail_hint: "Appuyez sur Entrée pour arrêter de suivre la sortie.".to_string(),
+            invalid_item: "Veuillez saisir le numéro d'un élément.".to_string(),
+            password_mismatch: "Les mots de passe ne correspondent pas.".to_string(),
+            password_too_short: "Le mot de passe doit contenir au moins {} caractères.".to_string(),
+            missing_lowercase: "Le mot de passe doit contenir une lettre minuscule.".to_string(),
+            missing_uppercase: "Le mot de passe doit contenir une lettre majuscule.".to_string(),
+            missing_digit: "Le mot de passe doit contenir un chiffre.".to_string(),
+            missing_symbol: "Le mot de passe doit contenir un symbole.".to_string(),
+        }
+    }
+
+    /// Returns the built-in catalog for the given locale name.
+    ///
+    /// The locale name has the form `language[_territory][.codeset][@modifier]`, for example
+    /// `de_DE.UTF-8`.  Only the language is considered.  If there is no catalog for the language,
+    /// the English catalog is returned.
+    pub fn for_locale(locale: &str) -> Catalog {
+        let language = locale.split(['_', '.', '@']).next().unwrap_or_default();
+        match language.to_lowercase().as_ref() {
+            "de" => Catalog::german(),
+            "fr" => Catalog::french(),
+            _ => Catalog::english(),
+        }
+    }
+
+    /// Returns the built-in catalog for the locale set in the environment.
+    ///
+    /// The locale is read from the first non-empty variable of `LC_ALL`, `LC_MESSAGES` and
+    /// `LANG`.  If none of these variables is set, the English catalog is returned.
+    pub fn from_env() -> Catalog {
+        Catalog::from_vars(|name| env::var(name).ok())
+    }
+
+    /// Returns the built-in catalog for the locale set in the variables returned by the given
+    /// function.
+    fn from_vars(var: impl Fn(&str) -> Option<String>) -> Catalog {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| var(name))
+            .find(|value| !value.is_empty())
+            .map(|locale| Catalog::for_locale(&locale))
+            .unwrap_or_else(Catalog::english)
+    }
+
+    /// Sets the answers that are accepted for yes and no.
+    ///
+    /// The answers are compared case-insensitively.  The first answer of each list is used in the
+    /// prompt, so it should be a short form like `y`.
+    pub fn set_answers(&mut self, yes: &[&str], no: &[&str]) {
+        self.yes = strings(yes);
+        self.no = strings(no);
+    }
+
+    /// Sets the label that is displayed in front of the default value of an input dialog.
+    pub fn set_default_label(&mut self, label: impl Into<String>) {
+        self.default_label = label.into();
+    }
+
+    /// Sets the message that is displayed if the answer to a question is not recognized.
+    pub fn set_invalid_answer(&mut self, message: impl Into<String>) {
+        self.invalid_answer = message.into();
+    }
+
+    /// Sets the hint that explains how to finish a multi-line text input.
+    pub fn set_text_input_hint(&mut self, hint: impl Into<String>) {
+        self.text_input_hint = hint.into();
+    }
+
+    /// Sets the prompt that is displayed at the end of a page of a long text.
+    ///
+    /// The user can quit the pager by entering `q`.
+    pub fn set_more_prompt(&mut self, prompt: impl Into<String>) {
+        self.more_prompt = prompt.into();
+    }
+
+    /// Sets the hint that explains how to stop following a file in a tail dialog.
+    pub fn set_tail_hint(&mut self, hint: impl Into<String>) {
+        self.tail_hint = hint.into();
+    }
+
+    /// Sets the message that is displayed if the answer to a menu is not the number of an item.
+    pub fn set_invalid_item(&mut self, message: impl Into<String>) {
+        self.invalid_item = message.into();
+    }
+
+    /// Sets the message that is displayed if the password and its confirmation do not match.
+    pub fn set_password_mismatch(&mut self, message: impl Into<String>) {
+        self.password_mismatch = message.into();
+    }
+
+    /// Sets the message that is displayed if a password is shorter than the minimum length.
+    ///
+    /// The first `{}` in the message is replaced with the minimum length.
+    pub fn set_password_too_short(&mut self, message: impl Into<String>) {
+        self.password_too_short = message.into();
+    }
+
+    /// Sets the message that is displayed if a password does not contain a character of the
+    /// given class.
+    pub fn set_password_missing(&mut self, class: CharClass, message: impl Into<String>) {
+        *self.missing(class) = message.into();
+    }
+
+    pub(crate) fn default_label(&self) -> &str {
+        &self.default_label
+    }
+
+    pub(crate) fn invalid_answer(&self) -> &str {
+        &self.invalid_answer
+    }
+
+    pub(crate) fn text_input_hint(&self) -> &str {
+        &self.text_input_hint
+    }
+
+    pub(crate) fn more_prompt(&self) -> &str {
+        &self.more_prompt
+    }
+
+    pub(crate) fn tail_hint(&self) -> &str {
+        &self.tail_hint
+    }
+
+    pub(crate) fn invalid_item(&self) -> &str {
+        &self.invalid_item
+    }
+
+    pub(crate) fn password_mismatch(&self) -> &str {
+        &self.password_mismatch
+    }
+
+    pub(crate) fn password_too_short(&self, length: usize) -> String {
+        self.password_too_short
+            .replacen("{}", &length.to_string(), 1)
+    }
+
+    pub(crate) fn password_missing(&self, class: CharClass) -> &str {
+        match class {
+            CharClass::Lowercase => &self.missing_lowercase,
+            CharClass::Uppercase => &self.missing_uppercase,
+            CharClass::Digit => &self.missing_digit,
+            CharClass::Symbol => &self.missing_symbol,
+        }
+    }
+
+    fn missing(&mut self, class: CharClass) -> &mut String {
+        match class {
+            CharClass::Lowercase => &mut self.missing_lowercase,
+            CharClass::Uppercase => &mut self.missing_uppercase,
+            CharClass::Digit => &mut self.missing_digit,
+            CharClass::Symbol => &mut self.missing_symbol,
+        }
+    }
+
+    /// Returns the options for a question prompt, with the default choice in upper case.
+    pub(crate) fn options(&self, default: Option<Choice>) -> String {
+        let yes = self.yes.first().map(String::as_str).unwrap_or_default();
+        let no = self.no.first().map(String::as_str).unwrap_or_default();
+        match default {
+            Some(Choice::Yes) => format!("{}/{}", yes.to_uppercase(), no),
+            Some(Choice::No) => format!("{}/{}", yes, no.to_uppercase()),
+            _ => format!("{}/{}", yes, no),
+        }
+    }
+
+    /// Returns the options for a prompt that requires the user to type the full answer.
+    pub(crate) fn confirmation_options(&self) -> String {
+        format!("{}/{}", full_answer(&self.yes), full_answer(&self.no))
+    }
+
+    /// Returns whether the given input is the full yes answer.
+    pub(crate) fn is_confirmation(&self, input: &str) -> bool {
+        input.trim().to_lowercase() == full_answer(&self.yes).to_lowercase()
+    }
+
+    pub(crate) fn parse_choice(&self, input: &str) -> Option<Choice> {
+        let input = input.trim().to_lowercase();
+        if self.yes.iter().any(|answer| answer.to_lowercase() == input) {
+            Some(Choice::Yes)
+        } else if self.no.iter().any(|answer| answer.to_lowercase() == input) {
+            Some(Choice::No)
+        } else {
+            None
+        }
+    }
+}
+
+impl Default for Catalog {
+    fn default() -> Self {
+        Catalog::from_env()
+    }
+}
+
+/// Returns the first answer that is not abbreviated to a single character.
+fn full_answer(answers: &[String]) -> &str {
+    answers
+        .iter()
+        .find(|answer| answer.chars().count() > 1)
+        .or_else(|| answers.first())
+        .map(String::as_str)
+        .unwrap_or_default()
+}
+
+fn strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Catalog;
+    use crate::Choice;
+
+    fn from_vars(vars: &[(&str, &str)]) -> Catalog {
+        Catalog::from_vars(|name| {
+            vars.iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string())
+        })
+    }
+
+    #[test]
+    fn locale_order() {
+        let vars = [
+            ("LC_ALL", "fr_FR.UTF-8"),
+            ("LC_MESSAGES", "de_DE.UTF-8"),
+            ("LANG", "en_US.UTF-8"),
+        ];
+        assert_eq!(Catalog::french(), from_vars(&vars));
+        assert_eq!(Catalog::german(), from_vars(&vars[1..]));
+        assert_eq!(Catalog::english(), from_vars(&vars[2..]));
+        assert_eq!(Catalog::english(), from_vars(&[]));
+    }
+
+    #[test]
+    fn locale_empty_variables() {
+        let vars = [("LC_ALL", ""), ("LC_MESSAGES", ""), ("LANG", "de_AT")];
+        assert_eq!(Catalog::german(), from_vars(&vars));
+    }
+
+    #[test]
+    fn locale_fallback() {
+        assert_eq!(
+            Catalog::english(),
+            from_vars(&[("LC_ALL", "C"), ("LANG", "de_DE")])
+        );
+        assert_eq!(
+            Catalog::english(),
+            from_vars(&[("LC_ALL", "POSIX"), ("LANG", "fr_FR")])
+        );
+        assert_eq!(Catalog::english(), Catalog::for_locale("C.UTF-8"));
+        assert_eq!(Catalog::english(), Catalog::for_locale("es_ES.UTF-8"));
+    }
+
+    #[test]
+    fn locale_names() {
+        assert_eq!(Catalog::german(), Catalog::for_locale("de"));
+        assert_eq!(Catalog::german(), Catalog::for_locale("de_CH.ISO-8859-1"));
+        assert_eq!(Catalog::french(), Catalog::for_locale("fr@euro"));
+        assert_eq!(Catalog::french(), Catalog::for_locale("FR_ca"));
+    }
+
+    #[test]
+    fn parse_choice() {
+        let german = Catalog::german();
+        assert_eq!(Some(Choice::Yes), german.parse_choice("j"));
+        assert_eq!(Some(Choice::Yes), german.parse_choice(" Ja "));
+        assert_eq!(Some(Choice::Yes), german.parse_choice("yes"));
+        assert_eq!(Some(Choice::No), german.parse_choice("NEIN"));
+        assert_eq!(None, german.parse_choice("oui"));
+
+        let french = Catalog::french();
+        assert_eq!(Some(Choice::Yes), french.parse_choice("o"));
+        assert_eq!(Some(Choice::Yes), french.parse_choice("Oui"));
+        assert_eq!(Some(Choice::No), french.parse_choice("non"));
+        assert_eq!(None, french.parse_choice("ja"));
+
+        let english = Catalog::english();
+        assert_eq!(Some(Choice::No), english.parse_choice("n"));
+        assert_eq!(None, english.parse_choice("j"));
+        assert_eq!(None, english.parse_choice(""));
+    }
+
+    #[test]
+    fn confirmation() {
+        let german = Catalog::german();
+        assert_eq!("ja/nein", german.confirmation_options());
+        assert!(german.is_confirmation("JA"));
+        assert!(!german.is_confirmation("j"));
+        assert_eq!("j/N", german.options(Some(Choice::No)));
+    }
+}
@@ -0,0 +1,400 @@
+// Copyright (C) 2019 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: MIT
+
+//! A minimal line editor for the `Stdio` backend.
+//!
+//! The editor puts the terminal into raw mode and supports the most common readline key bindings:
+//! - Left/Right, Ctrl-B/Ctrl-F: move the cursor
+//! - Home/End, Ctrl-A/Ctrl-E: move the cursor to the start or end of the line
+//! - Backspace, Delete: delete the character before or under the cursor
+//! - Ctrl-W, Alt-Backspace: delete the word before the cursor
+//! - Ctrl-U/Ctrl-K: delete the text before or after the cursor
+//! - Up/Down, Ctrl-P/Ctrl-N: browse the history
+//! - Ctrl-C, Ctrl-D on an empty line: cancel the input
+
+use std::fs;
+use std::io::{self, Read, Write};
+use std::mem;
+use std::os::unix::io::AsRawFd;
+
+use crate::backends::stdio::render;
+use crate::Result;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Key {
+    Char(char),
+    Enter,
+    Interrupt,
+    Eof,
+    Left,
+    Right,
+    Home,
+    End,
+    Up,
+    Down,
+    Backspace,
+    Delete,
+    DeleteWord,
+    KillStart,
+    KillEnd,
+    Unknown,
+}
+
+/// Puts a terminal into raw mode and restores the previous settings when dropped.
+struct RawMode {
+    fd: i32,
+    orig: libc::termios,
+}
+
+impl RawMode {
+    fn enable(terminal: &fs::File) -> io::Result<RawMode> {
+        let fd = terminal.as_raw_fd();
+        // SAFETY: termios is a plain C struct that is fully initialized by tcgetattr.
+        let mut term: libc::termios = unsafe { mem::zeroed() };
+        if unsafe { libc::tcgetattr(fd, &mut term) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let orig = term;
+        term.c_iflag &= !(libc::ICRNL | libc::IXON);
+        term.c_lflag &= !(libc::ECHO | libc::ICANON | libc::ISIG | libc::IEXTEN);
+        term.c_cc[libc::VMIN] = 1;
+        term.c_cc[libc::VTIME] = 0;
+        if unsafe { libc::tcsetattr(fd, libc::TCSANOW, &term) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(RawMode { fd, orig })
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        unsafe {
+            libc::tcsetattr(self.fd, libc::TCSANOW, &self.orig);
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Line {
+    chars: Vec<char>,
+    cursor: usize,
+}
+
+impl Line {
+    fn set(&mut self, s: &str) {
+        self.chars = s.chars().collect();
+        self.cursor = self.chars.len();
+    }
+
+    fn insert(&mut self, c: char) {
+        self.chars.insert(self.cursor, c);
+        self.cursor += 1;
+    }
+
+    fn backspace(&mut self) {
+        if self.cursor > 0 {
+            self.cursor -= 1;
+            self.chars.remove(self.cursor);
+        }
+    }
+
+    fn delete(&mut self) {
+        if self.cursor < self.chars.len() {
+            self.chars.remove(self.cursor);
+        }
+    }
+
+    fn delete_word(&mut self) {
+        let mut start = self.cursor;
+        while start > 0 && self.chars[start - 1].is_whitespace() {
+            start -= 1;
+        }
+        while start > 0 && !self.chars[start - 1].is_whitespace() {
+            start -= 1;
+        }
+        self.chars.drain(start..self.cursor);
+        self.cursor = start;
+    }
+
+    fn before_cursor(&self) -> String {
+        self.chars[..self.cursor].iter().collect()
+    }
+
+    fn text(&self) -> String {
+        self.chars.iter().collect()
+    }
+}
+
+/// Reads a line from the given terminal with line editing.
+///
+/// The prompt must not contain line breaks.  The line is initialized with the given text, and
+/// the history entries can be recalled with the arrow keys.  Returns `None` if the user cancelled
+/// the input.
+pub(crate) fn read_line(
+    terminal: &fs::File,
+    out: &mut dyn Write,
+    prompt: &str,
+    initial: &str,
+    history: &[String],
+) -> Result<Option<String>> {
+    let _raw_mode = RawMode::enable(terminal)?;
+    let mut input = terminal;
+    let mut line = Line::default();
+    line.set(initial);
+    let mut position = history.len();
+    let mut draft = String::new();
+
+    loop {
+        draw(out, prompt, &line)?;
+        match read_key(&mut input)? {
+            Key::Char(c) => line.insert(c),
+            Key::Enter => {
+                write!(out, "\r\n")?;
+                return Ok(Some(line.text()));
+            }
+            Key::Interrupt => {
+                write!(out, "\r\n")?;
+                return Ok(None);
+            }
+            Key::Eof if line.chars.is_empty() => {
+                write!(out, "\r\n")?;
+                return Ok(None);
+            }
+            Key::Eof | Key::Delete => line.delete(),
+            Key::Left => line.cursor = line.cursor.saturating_sub(1),
+            Key::Right => line.cursor = (line.cursor + 1).min(line.chars.len()),
+            Key::Home => line.cursor = 0,
+            Key::End => line.cursor = line.chars.len(),
+            Key::Up if position > 0 => {
+                if position == history.len() {
+                    draft = line.text();
+                }
+                position -= 1;
+                line.set(&history[position]);
+            }
+            Key::Down if position < history.len() => {
+                position += 1;
+                if position == history.len() {
+                    line.set(&draft);
+                } else {
+                    line.set(&history[position]);
+                }
+            }
+            Key::Backspace => line.backspace(),
+            Key::DeleteWord => line.delete_word(),
+            Key::KillStart => {
+                line.chars.drain(..line.cursor);
+                line.cursor = 0;
+            }
+            Key::KillEnd => line.chars.truncate(line.cursor),
+            Key::Up | Key::Down | Key::Unknown => {}
+        }
+    }
+}
+
+fn draw(out: &mut dyn Write, prompt: &str, line: &Line) -> io::Result<()> {
+    write!(out, "\r{}{}\x1b[K\r", prompt, line.text())?;
+    let column = render::width(prompt) + render::width(&line.before_cursor());
+    if column > 0 {
+        write!(out, "\x1b[{}C", column)?;
+    }
+    out.flush()
+}
+
+fn read_byte(input: &mut impl Read) -> io::Result<Option<u8>> {
+    let mut buf = [0; 1];
+    match input.read(&mut buf)? {
+        0 => Ok(None),
+        _ => Ok(Some(buf[0])),
+    }
+}
+
+fn read_key(input: &mut impl Read) -> io::Result<Key> {
+    let byte = match read_byte(input)? {
+        Some(byte) => byte,
+        // The terminal has been closed, so there is no way to continue editing
+        None => return Ok(Key::Interrupt),
+    };
+    let key = match byte {
+        0x01 => Key::Home,
+        0x02 => Key::Left,
+        0x03 => Key::Interrupt,
+        0x04 => Key::Eof,
+        0x05 => Key::End,
+        0x06 => Key::Right,
+        0x08 | 0x7f => Key::Backspace,
+        0x0a | 0x0d => Key::Enter,
+        0x0b => Key::KillEnd,
+        0x0e => Key::Down,
+        0x10 => Key::Up,
+        0x15 => Key::KillStart,
+        0x17 => Key::DeleteWord,
+        0x1b => read_escape_sequence(input)?,
+        byte if byte < 0x20 => Key::Unknown,
+        byte => read_char(input, byte)?,
+    };
+    Ok(key)
+}
+
+fn read_escape_sequence(input: &mut impl Read) -> io::Result<Key> {
+    match read_byte(input)? {
+        Some(b'[') => {
+            let mut params = Vec::new();
+            loop {
+                match read_byte(input)? {
+                    Some(byte) if (0x40..=0x7e).contains(&byte) => {
+                        params.push(byte);
+                        break;
+                    }
+                    Some(byte) => params.push(byte),
+                    None => return Ok(Key::Unknown),
+                }
+            }
+            let key = match params.as_slice() {
+                b"A" => Key::Up,
+                b"B" => Key::Down,
+                b"C" => Key::Right,
+                b"D" => Key::Left,
+                b"H" | b"1~" | b"7~" => Key::Home,
+                b"F" | b"4~" | b"8~" => Key::End,
+                b"3~" => Key::Delete,
+                _ => Key::Unknown,
+            };
+            Ok(key)
+        }
+        Some(b'O') => {
+            let key = match read_byte(input)? {
+                Some(b'A') => Key::Up,
+                Some(b'B') => Key::Down,
+                Some(b'C') => Key::Right,
+                Some(b'D') => Key::Left,
+                Some(b'H') => Key::Home,
+                Some(b'F') => Key::End,
+                _ => Key::Unknown,
+            };
+            Ok(key)
+        }
+        Some(0x7f) => Ok(Key::DeleteWord),
+        _ => Ok(Key::Unknown),
+    }
+}
+
+fn read_char(input: &mut impl Read, first: u8) -> io::Result<Key> {
+    let len = match first {
+        0x00..=0x7f => 1,
+        0xc0..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xf7 => 4,
+        _ => return Ok(Key::Unknown),
+    };
+    let mut bytes = vec![first];
+    for _ in 1..len {
+        match read_byte(input)? {
+            Some(byte) => bytes.push(byte),
+            None => return Ok(Key::Unknown),
+        }
+    }
+    Ok(std::str::from_utf8(&bytes)
+        .ok()
+        .and_then(|s| s.chars().next())
+        .map(Key::Char)
+        .unwrap_or(Key::Unknown))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{draw, read_key, Key, Line};
+
+    fn keys(mut input: &[u8]) -> Vec<Key> {
+        let mut keys = Vec::new();
+        while !input.is_empty() {
+            keys.push(read_key(&mut input).unwrap());
+        }
+        keys
+    }
+
+    #[test]
+    fn read_control_keys() {
+        assert_eq!(
+            vec![
+                Key::Home,
+                Key::Interrupt,
+                Key::Eof,
+                Key::End,
+                Key::Enter,
+                Key::Enter
+            ],
+            keys(b"\x01\x03\x04\x05\r\n")
+        );
+        assert_eq!(
+            vec![
+                Key::Backspace,
+                Key::Backspace,
+                Key::DeleteWord,
+                Key::KillStart
+            ],
+            keys(b"\x08\x7f\x17\x15")
+        );
+        assert_eq!(Key::Interrupt, read_key(&mut &b""[..]).unwrap());
+    }
+
+    #[test]
+    fn read_escape_sequences() {
+        assert_eq!(
+            vec![Key::Up, Key::Down, Key::Right, Key::Left],
+            keys(b"\x1b[A\x1b[B\x1b[C\x1b[D")
+        );
+        assert_eq!(
+            vec![Key::Home, Key::End, Key::Home, Key::End, Key::Delete],
+            keys(b"\x1b[H\x1b[F\x1b[1~\x1b[4~\x1b[3~")
+        );
+        assert_eq!(vec![Key::Up, Key::Home], keys(b"\x1bOA\x1bOH"));
+        assert_eq!(vec![Key::DeleteWord], keys(b"\x1b\x7f"));
+        // Unknown sequences are consumed completely
+        assert_eq!(vec![Key::Unknown, Key::Char('a')], keys(b"\x1b[1;5Ca"));
+        assert_eq!(vec![Key::Unknown], keys(b"\x1b[1"));
+    }
+
+    #[test]
+    fn read_chars() {
+        assert_eq!(
+            vec![
+                Key::Char('a'),
+                Key::Char('ä'),
+                Key::Char('日'),
+                Key::Char('🦀')
+            ],
+            keys("aä日🦀".as_bytes())
+        );
+        assert_eq!(vec![Key::Unknown], keys(b"\xff"));
+        assert_eq!(vec![Key::Unknown], keys(b"\xc3"));
+    }
+
+    #[test]
+    fn edit_line() {
+        let mut line = Line::default();
+        line.set("hello world");
+        line.delete_word();
+        assert_eq!("hello ", line.text());
+        line.insert('x');
+        line.backspace();
+        line.backspace();
+        assert_eq!("hello", line.text());
+        line.cursor = 0;
+        line.delete();
+        line.backspace();
+        assert_eq!("ello", line.text());
+        assert_eq!("", line.before_cursor());
+    }
+
+    #[test]
+    fn draw_line() {
+        let mut line = Line::default();
+        line.set("日本");
+        line.cursor = 1;
+        let mut output = Vec::new();
+        draw(&mut output, "> ", &line).unwrap();
+        // The cursor is placed after the first character, which takes two columns
+        assert_eq!("\r> 日本\x1b[K\r\x1b[4C".as_bytes(), &output[..]);
+    }
+}
@@ -0,0 +1,207 @@
+// Copyright (C) 2019 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: MIT
+
+//! Persistent input history for the `Stdio` backend.
+//!
+//! The history for a dialog ID is stored in the file `dialog-rs/history/<id>` in the XDG data
+//! directory, which is `$XDG_DATA_HOME` or `~/.local/share`, with special characters in the ID
+//! percent-encoded.  Errors while reading or writing the
+//! history are ignored so that they do not prevent the dialog from being shown.  On Unix systems,
+//! the history files are only accessible by the user.
+
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::path;
+
+/// The maximum number of entries kept per dialog ID.
+const MAX_ENTRIES: usize = 100;
+
+/// Returns the path of the history file for the given dialog ID.
+fn path(id: &str) -> Option<path::PathBuf> {
+    let data_dir = env::var_os("XDG_DATA_HOME")
+        .map(path::PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| env::var_os("HOME").map(|home| path::Path::new(&home).join(".local/share")))?;
+    Some(
+        data_dir
+            .join("dialog-rs")
+            .join("history")
+            .join(file_name(id)?),
+    )
+}
+
+/// Returns the name of the history file for the given dialog ID.
+///
+/// All bytes except ASCII letters, digits, `-`, `_` and `.` are percent-encoded so that distinct
+/// IDs are mapped to distinct file names.  IDs that only consist of dots are encoded completely
+/// so that they do not refer to a directory.  There is no history file for an empty ID.
+fn file_name(id: &str) -> Option<String> {
+    if id.is_empty() {
+        return None;
+    }
+    let dots_only = id.bytes().all(|b| b == b'.');
+    let mut file_name = String::with_capacity(id.len());
+    for b in id.bytes() {
+        match b {
+            b'.' if !dots_only => file_name.push('.'),
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'_' => file_name.push(b.into()),
+            _ => file_name.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    Some(file_name)
+}
+
+/// Loads the history entries for the given dialog ID, oldest first.
+pub(crate) fn load(id: &str) -> Vec<String> {
+    path(id).map(|path| read(&path)).unwrap_or_default()
+}
+
+/// Reads the history entries from the given file, oldest first.
+fn read(path: &path::Path) -> Vec<String> {
+    fs::read_to_string(path)
+        .map(|content| content.lines().map(ToOwned::to_owned).collect())
+        .unwrap_or_default()
+}
+
+/// Adds an entry to the history for the given dialog ID.
+pub(crate) fn add(id: &str, entry: &str) {
+    if let Some(path) = path(id) {
+        append(&path, entry);
+    }
+}
+
+/// Adds an entry to the history file at the given path, keeping at most `MAX_ENTRIES` entries.
+///
+/// Empty entries, entries containing line breaks and repetitions of the last entry are skipped.
+fn append(path: &path::Path, entry: &str) {
+    if entry.is_empty() || entry.contains('\n') {
+        return;
+    }
+
+    let mut entries = read(path);
+    if entries.last().map(String::as_str) == Some(entry) {
+        return;
+    }
+    entries.push(entry.to_string());
+    let skip = entries.len().saturating_sub(MAX_ENTRIES);
+
+    let _ = path
+        .parent()
+        .map(fs::create_dir_all)
+        .unwrap_or(Ok(()))
+        .and_then(|_| create(path))
+        .and_then(|mut file| {
+            for entry in &entries[skip..] {
+                writeln!(file, "{}", entry)?;
+            }
+            Ok(())
+        });
+}
+
+/// Creates or truncates the history file at the given path so that it is only accessible by the
+/// user.
+fn create(path: &path::Path) -> io::Result<fs::File> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let file = options.open(path)?;
+    // Restrict the permissions of history files that have been created by older versions
+    #[cfg(unix)]
+    file.set_permissions(std::os::unix::fs::PermissionsExt::from_mode(0o600))?;
+    Ok(file)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+    use std::path;
+    use std::process;
+
+    /// Returns the path of a history file in a new test directory with the given name.
+    fn test_file(name: &str) -> path::PathBuf {
+        let dir = env::temp_dir().join(format!("dialog-rs-test-{}-{}", process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        dir.join("history").join("id")
+    }
+
+    fn remove(path: &path::Path) {
+        let dir = path.parent().and_then(path::Path::parent).unwrap();
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn file_name() {
+        assert_eq!(None, super::file_name(""));
+        assert_eq!(
+            Some("my-app_1.name"),
+            super::file_name("my-app_1.name").as_deref()
+        );
+        assert_eq!(Some("a%2Fb"), super::file_name("a/b").as_deref());
+        assert_eq!(Some("a_b"), super::file_name("a_b").as_deref());
+        assert_eq!(Some("a%25b"), super::file_name("a%b").as_deref());
+        assert_eq!(Some("%C3%A4%20"), super::file_name("ä ").as_deref());
+        assert_eq!(Some("%2E"), super::file_name(".").as_deref());
+        assert_eq!(Some("%2E%2E"), super::file_name("..").as_deref());
+        assert_eq!(Some("..a"), super::file_name("..a").as_deref());
+    }
+
+    #[test]
+    fn file_name_is_injective() {
+        let ids = [
+            "a/b", "a_b", "a%2Fb", "a b", "a%20b", ".", "%2E", "..", "a.b",
+        ];
+        let mut names: Vec<_> = ids.iter().map(|id| super::file_name(id)).collect();
+        names.sort();
+        names.dedup();
+        assert_eq!(ids.len(), names.len());
+    }
+
+    #[test]
+    fn append() {
+        let path = test_file("history-append");
+        super::append(&path, "one");
+        super::append(&path, "");
+        super::append(&path, "two\nlines");
+        super::append(&path, "two");
+        super::append(&path, "two");
+        super::append(&path, "one");
+        assert_eq!(vec!["one", "two", "one"], super::read(&path));
+        remove(&path);
+    }
+
+    #[test]
+    fn append_max_entries() {
+        let path = test_file("history-max");
+        for i in 0..super::MAX_ENTRIES + 5 {
+            super::append(&path, &i.to_string());
+        }
+        let entries = super::read(&path);
+        assert_eq!(super::MAX_ENTRIES, entries.len());
+        assert_eq!(Some("5"), entries.first().map(String::as_str));
+        assert_eq!(
+            Some((super::MAX_ENTRIES + 4).to_string()),
+            entries.last().cloned()
+        );
+        remove(&path);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn append_mode() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = test_file("history-mode");
+        super::append(&path, "one");
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(0o600, mode & 0o777);
+
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+        super::append(&path, "two");
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(0o600, mode & 0o777);
+        remove(&path);
+    }
+}
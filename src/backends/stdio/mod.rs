@@ -0,0 +1,871 @@
+// Copyright (C) 2019 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: MIT
+
+mod catalog;
+#[cfg(unix)]
+mod editor;
+mod history;
+mod render;
+mod terminal;
+
+pub use crate::backends::stdio::catalog::Catalog;
+
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Read, Write};
+use std::process;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::thread;
+
+use zeroize::Zeroize;
+
+use crate::{
+    Choice, Credentials, Error, FieldKind, Form, Input, License, Login, Menu, Message, Password,
+    ProgramBox, Question, Result, Secret, Tail, TextInput, TextViewer,
+};
+
+/// The policy for reading passwords if the input of a [`Stdio`][] backend is not a terminal.
+///
+/// If the input is a terminal, passwords are always read with the echo disabled.  Otherwise, the
+/// policy determines how the password is read, see [`Stdio::set_password_fallback`][].
+///
+/// [`Stdio`]: struct.Stdio.html
+/// [`Stdio::set_password_fallback`]: struct.Stdio.html#method.set_password_fallback
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PasswordFallback {
+    /// Read the password as a line from the input, for example from a pipe.
+    ///
+    /// The password is read like any other input, so this should only be used if the password
+    /// is not entered by a user, for example in automated scripts.
+    Input,
+    /// Read the password from the controlling terminal (`/dev/tty`) with the echo disabled.
+    ///
+    /// If there is no controlling terminal, a [`NonInteractive`][] error is returned.
+    ///
+    /// [`NonInteractive`]: ../enum.Error.html#variant.NonInteractive
+    Tty,
+    /// Return a [`NonInteractive`][] error.
+    ///
+    /// [`NonInteractive`]: ../enum.Error.html#variant.NonInteractive
+    Error,
+}
+
+/// The fallback backend using standard input and output.
+///
+/// This backend is intended as a fallback backend to use if no other backend is available.  The
+/// dialogs are printed to a writer and user input is read from a reader.  Per default, the
+/// standard input and output are used if both are attached to a terminal.  Otherwise, the
+/// controlling terminal (`/dev/tty`) is used, so that the prompts are not mixed with the output of
+/// the program and the answers are not read from piped data.  If there is no controlling
+/// terminal, the prompts are written to the standard error and the answers are read from the
+/// standard input.
+///
+/// The reader and writer can be customized with [`with_io`][], for example to drive the dialogs
+/// from tests or a network socket.  The prompts are localized according to the locale set in the
+/// environment, see [`Catalog`][].
+///
+/// If the output is a terminal, long texts are wrapped to the width of the terminal.  Optionally,
+/// the dialogs can be drawn in frames and titles can be highlighted with colors, see
+/// [`set_framed`][] and [`set_colored`][].  These settings are ignored if the output is not a
+/// terminal.
+///
+/// If both the input and the output are a terminal, the input dialogs support line editing with
+/// the common readline key bindings, for example the arrow keys, Home and End, Ctrl-W to delete
+/// a word and Ctrl-U to delete the line.  The default value is pre-filled in the input field and
+/// can be edited in place.  If an input history is enabled for an input dialog using
+/// [`Input::history`][], previous entries can be recalled with the Up and Down keys.  The history
+/// is stored in the `dialog-rs/history` directory in the XDG data directory.  Line editing can
+/// be disabled with [`set_line_editing`][].
+///
+/// # Example
+///
+/// ```
+/// use dialog::DialogBox;
+///
+/// let mut output = Vec::new();
+/// let backend = dialog::backends::Stdio::with_io(&b"Alice\n"[..], &mut output);
+/// let name = dialog::Input::new("Please enter your name")
+///     .show_with(&backend)
+///     .expect("Could not display dialog box");
+/// assert_eq!(Some("Alice".to_string()), name);
+/// ```
+///
+/// [`with_io`]: #method.with_io
+/// [`Catalog`]: struct.Catalog.html
+/// [`set_framed`]: #method.set_framed
+/// [`set_colored`]: #method.set_colored
+/// [`set_line_editing`]: #method.set_line_editing
+/// [`Input::history`]: ../struct.Input.html#method.history
+pub struct Stdio<R = Box<dyn BufRead + Send>, W = Box<dyn Write + Send>> {
+    reader: Mutex<R>,
+    writer: Mutex<W>,
+    // The terminal the input is read from, or None if the input is not interactive
+    terminal: Option<fs::File>,
+    // The terminal the output is written to, or None if the output is not a terminal
+    display: Option<fs::File>,
+    catalog: Catalog,
+    framed: bool,
+    colored: bool,
+    line_editing: bool,
+    password_fallback: PasswordFallback,
+}
+
+impl Stdio {
+    /// Creates a new `Stdio` instance.
+    ///
+    /// If the standard input and output are both terminals, they are used to display the dialogs.
+    /// Otherwise, the controlling terminal is used if available, or the standard error and the
+    /// standard input if not.
+    pub fn new() -> Stdio {
+        if io::stdin().is_terminal() && io::stdout().is_terminal() {
+            Stdio::standard()
+        } else {
+            Stdio::terminal().unwrap_or_else(|_| {
+                let display = if io::stderr().is_terminal() {
+                    terminal::handle(&io::stderr())
+                } else {
+                    None
+                };
+                Stdio::from_parts(
+                    Box::new(io::BufReader::new(io::stdin())),
+                    Box::new(io::stderr()),
+                    None,
+                    display,
+                )
+            })
+        }
+    }
+
+    /// Creates a new `Stdio` instance using the standard input and output.
+    pub fn standard() -> Stdio {
+        let terminal = if io::stdin().is_terminal() {
+            terminal::handle(&io::stdin())
+        } else {
+            None
+        };
+        let display = if io::stdout().is_terminal() {
+            terminal::handle(&io::stdout())
+        } else {
+            None
+        };
+        Stdio::from_parts(
+            Box::new(io::BufReader::new(io::stdin())),
+            Box::new(io::stdout()),
+            terminal,
+            display,
+        )
+    }
+
+    /// Creates a new `Stdio` instance using the controlling terminal, `/dev/tty`.
+    ///
+    /// This function returns an error if the process does not have a controlling terminal.
+    pub fn terminal() -> Result<Stdio> {
+        let tty = fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open("/dev/tty")?;
+        let reader = tty.try_clone()?;
+        let terminal = tty.try_clone()?;
+        let display = tty.try_clone()?;
+        Ok(Stdio::from_parts(
+            Box::new(io::BufReader::new(reader)),
+            Box::new(tty),
+            Some(terminal),
+            Some(display),
+        ))
+    }
+}
+
+impl<R: BufRead, W: Write> Stdio<R, W> {
+    /// Creates a new `Stdio` instance that reads from the given reader and writes to the given
+    /// writer.
+    ///
+    /// Passwords are read from the reader like other input, so they are not hidden, see
+    /// [`PasswordFallback::Input`][].  The output is not treated as a terminal, so texts are not
+    /// wrapped and no frames or colors are used.  The backend can be shared between threads if
+    /// the reader and the writer are `Send`.
+    ///
+    /// [`PasswordFallback::Input`]: enum.PasswordFallback.html#variant.Input
+    pub fn with_io(reader: R, writer: W) -> Stdio<R, W> {
+        let mut stdio = Stdio::from_parts(reader, writer, None, None);
+        stdio.password_fallback = PasswordFallback::Input;
+        stdio
+    }
+
+    /// Locks the reader, ignoring a panic in a previous dialog.
+    fn reader(&self) -> MutexGuard<'_, R> {
+        self.reader.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Locks the writer, ignoring a panic in a previous dialog.
+    fn writer(&self) -> MutexGuard<'_, W> {
+        self.writer.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn from_parts(
+        reader: R,
+        writer: W,
+        terminal: Option<fs::File>,
+        display: Option<fs::File>,
+    ) -> Stdio<R, W> {
+        Stdio {
+            reader: Mutex::new(reader),
+            writer: Mutex::new(writer),
+            terminal,
+            display,
+            catalog: Catalog::from_env(),
+            framed: false,
+            colored: false,
+            line_editing: true,
+            password_fallback: PasswordFallback::Tty,
+        }
+    }
+
+    /// Sets whether the dialogs are drawn in frames.
+    ///
+    /// Frames are only drawn if the output is a terminal.  Per default, no frames are drawn.
+    pub fn set_framed(&mut self, framed: bool) {
+        self.framed = framed;
+    }
+
+    /// Sets whether colors are used to highlight titles and warnings.
+    ///
+    /// Colors are only used if the output is a terminal and the `NO_COLOR` environment variable
+    /// is not set.  Per default, no colors are used.
+    pub fn set_colored(&mut self, colored: bool) {
+        self.colored = colored;
+    }
+
+    /// Sets the catalog used to localize the prompts and to parse the answers.
+    ///
+    /// Per default, the catalog is chosen according to the locale set in the environment, see
+    /// [`Catalog::from_env`][].
+    ///
+    /// [`Catalog::from_env`]: struct.Catalog.html#method.from_env
+    pub fn set_catalog(&mut self, catalog: Catalog) {
+        self.catalog = catalog;
+    }
+
+    /// Returns the number of columns available for text, or `None` if the text should not be
+    /// wrapped.
+    fn line_width(&self) -> Option<usize> {
+        self.display
+            .as_ref()
+            .map(|display| terminal::width(display).unwrap_or(80))
+    }
+
+    fn is_framed(&self) -> bool {
+        self.framed && self.display.is_some()
+    }
+
+    fn paint(&self, s: &str, code: &str) -> String {
+        let no_color = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+        if self.colored && self.display.is_some() && !no_color {
+            format!("\x1b[{}m{}\x1b[0m", code, s)
+        } else {
+            s.to_string()
+        }
+    }
+
+    fn wrap(&self, text: &str, indent: usize) -> Vec<String> {
+        match self.line_width() {
+            Some(width) => render::wrap(text, width.saturating_sub(indent)),
+            None => text.split('\n').map(ToOwned::to_owned).collect(),
+        }
+    }
+
+    fn print_title(&self, title: &Option<String>) -> Result<()> {
+        if let Some(ref title) = title {
+            let mut writer = self.writer();
+            if self.is_framed() {
+                let lines = self.wrap(title, 4);
+                for line in render::frame(None, &lines, |s| s.to_string()) {
+                    writeln!(writer, "{}", line)?;
+                }
+            } else {
+                let lines = self.wrap(title, 0);
+                for line in &lines {
+                    writeln!(writer, "{}", self.paint(line, BOLD))?;
+                }
+                let width = lines.iter().map(|line| render::width(line)).max();
+                writeln!(writer, "{}", "=".repeat(width.unwrap_or(0)))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn print_text(&self, title: &Option<String>, text: &str) -> Result<()> {
+        if self.is_framed() {
+            let mut writer = self.writer();
+            let lines = self.wrap(text, 4);
+            // The title is displayed in the top border between "┌─ " and " ─┐"
+            let title = match (title, self.line_width()) {
+                (Some(title), Some(width)) => {
+                    Some(render::truncate(title, width.saturating_sub(6)))
+                }
+                (title, _) => title.clone(),
+            };
+            for line in render::frame(title.as_deref(), &lines, |s| self.paint(s, BOLD)) {
+                writeln!(writer, "{}", line)?;
+            }
+        } else {
+            self.print_title(title)?;
+            let mut writer = self.writer();
+            for line in self.wrap(text, 0) {
+                writeln!(writer, "{}", line)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Displays the given text page by page.
+    ///
+    /// If the output is a terminal and the `PAGER` environment variable is set, the pager is used.
+    /// Otherwise, the text is paged if both the input and the output are a terminal and printed
+    /// at once if not.
+    fn page(&self, title: &Option<String>, text: &str) -> Result<()> {
+        if let Some(ref display) = self.display {
+            if let Some(pager) = env::var("PAGER")
+                .ok()
+                .filter(|pager| !pager.trim().is_empty())
+            {
+                let mut content = String::new();
+                if let Some(ref title) = title {
+                    content.push_str(title);
+                    content.push('\n');
+                    content.push_str(&"=".repeat(render::width(title)));
+                    content.push_str("\n\n");
+                }
+                content.push_str(text);
+                return self.run_pager(&pager, display, &content);
+            }
+        }
+
+        self.print_title(title)?;
+        let lines = self.wrap(text.trim_end_matches('\n'), 0);
+        let page_height = match (&self.terminal, &self.display) {
+            (Some(_), Some(display)) => terminal::height(display).unwrap_or(24).saturating_sub(1),
+            _ => lines.len(),
+        };
+        let mut pages = lines.chunks(page_height.max(1)).peekable();
+        while let Some(page) = pages.next() {
+            {
+                let mut writer = self.writer();
+                for line in page {
+                    writeln!(writer, "{}", line)?;
+                }
+            }
+            if pages.peek().is_some() {
+                self.print_prompt(format_args!("{} ", self.catalog.more_prompt()))?;
+                match self.read_line()? {
+                    Some(answer) if answer.trim().eq_ignore_ascii_case("q") => break,
+                    Some(_) => {}
+                    None => break,
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Displays the given content in the given pager.
+    fn run_pager(&self, pager: &str, display: &fs::File, content: &str) -> Result<()> {
+        self.writer().flush()?;
+        let mut command = crate::backends::shell_command(pager, &[]);
+        command.stdin(process::Stdio::piped());
+        command.stdout(display.try_clone()?);
+        let mut child = command.spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            // The pager closes its input if the user quits before reaching the end
+            match stdin.write_all(content.as_bytes()) {
+                Err(err) if err.kind() != io::ErrorKind::BrokenPipe => return Err(err.into()),
+                _ => {}
+            }
+        }
+        let status = child.wait()?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(Error::from(("pager", status)))
+        }
+    }
+
+    /// Prints a warning, for example if the user entered an invalid answer.
+    fn print_warning(&self, warning: &str) -> Result<()> {
+        let mut writer = self.writer();
+        for line in self.wrap(warning, 0) {
+            writeln!(writer, "{}", self.paint(&line, YELLOW))?;
+        }
+        Ok(())
+    }
+
+    /// Wraps the given prompt text so that the last line can be followed by the user input.
+    fn prompt_text(&self, text: &str) -> String {
+        self.wrap(text, 0).join("\n")
+    }
+
+    /// Sets whether line editing is enabled for input dialogs.
+    ///
+    /// Line editing is only used if both the input and the output are a terminal.  Per default,
+    /// line editing is enabled.
+    pub fn set_line_editing(&mut self, line_editing: bool) {
+        self.line_editing = line_editing;
+    }
+
+    /// Sets the policy for reading passwords if the input is not a terminal.
+    ///
+    /// Per default, passwords are read from the controlling terminal, see
+    /// [`PasswordFallback::Tty`][].  For instances created with [`with_io`][], the default is
+    /// [`PasswordFallback::Input`][].
+    ///
+    /// # Example
+    ///
+    /// Read the password from the standard input in an automated script, for example with
+    /// `echo secret | tool`:
+    ///
+    /// ```no_run
+    /// use dialog::backends::{PasswordFallback, Stdio};
+    /// use dialog::DialogBox;
+    ///
+    /// let mut backend = Stdio::standard();
+    /// backend.set_password_fallback(PasswordFallback::Input);
+    /// let password = dialog::Password::new("Password")
+    ///     .show_with(&backend)
+    ///     .expect("Could not read password");
+    /// ```
+    ///
+    /// [`PasswordFallback::Tty`]: enum.PasswordFallback.html#variant.Tty
+    /// [`PasswordFallback::Input`]: enum.PasswordFallback.html#variant.Input
+    /// [`with_io`]: #method.with_io
+    pub fn set_password_fallback(&mut self, fallback: PasswordFallback) {
+        self.password_fallback = fallback;
+    }
+
+    fn print_prompt(&self, prompt: fmt::Arguments<'_>) -> Result<()> {
+        let mut writer = self.writer();
+        writer.write_fmt(prompt)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Returns whether line editing is available, i. e. enabled and supported by the terminal.
+    fn can_edit(&self) -> bool {
+        cfg!(unix) && self.line_editing && self.terminal.is_some() && self.display.is_some()
+    }
+
+    /// Reads a line with line editing.
+    ///
+    /// The prompt must not contain line breaks.  It is printed by the line editor.
+    #[cfg(unix)]
+    fn edit_line(&self, prompt: &str, initial: &str, history: &[String]) -> Result<Option<String>> {
+        match self.terminal {
+            Some(ref terminal) => {
+                let mut writer = self.writer();
+                editor::read_line(terminal, &mut *writer, prompt, initial, history)
+            }
+            None => Ok(None),
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn edit_line(
+        &self,
+        _prompt: &str,
+        _initial: &str,
+        _history: &[String],
+    ) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    fn read_input(&self, input: &Input) -> Result<Option<String>> {
+        let text = self.prompt_text(&input.text);
+        let (first_lines, last_line) = match text.rfind('\n') {
+            Some(idx) => (&text[..=idx], &text[idx + 1..]),
+            None => ("", text.as_str()),
+        };
+
+        if self.can_edit() {
+            let history = input
+                .history
+                .as_ref()
+                .map(|id| history::load(id))
+                .unwrap_or_default();
+            let initial = input.default.as_deref().unwrap_or("");
+            self.print_prompt(format_args!("{}", first_lines))?;
+            return self.edit_line(&format!("{}: ", last_line), initial, &history);
+        } else if let Some(ref default) = input.default {
+            self.print_prompt(format_args!(
+                "{} [{}: {}]: ",
+                text,
+                self.catalog.default_label(),
+                default
+            ))?;
+        } else {
+            self.print_prompt(format_args!("{}: ", text))?;
+        }
+
+        let user_input = self.read_line()?;
+        if user_input.as_ref().is_some_and(|s| s.is_empty()) {
+            if let Some(ref default) = input.default {
+                return Ok(Some(default.to_string()));
+            }
+        }
+        Ok(user_input)
+    }
+
+    /// Reads a line from the reader and returns `None` at the end of the input.
+    fn read_line(&self) -> Result<Option<String>> {
+        let input = read_line(&mut *self.reader())?;
+        // Move to the next line if the user pressed Ctrl-D in a terminal
+        if input.is_none() && self.terminal.is_some() {
+            writeln!(self.writer())?;
+        }
+        Ok(input)
+    }
+
+    #[cfg(unix)]
+    fn read_password(&self) -> Result<Option<Secret>> {
+        if let Some(ref terminal) = self.terminal {
+            let mut input = terminal;
+            return terminal::without_echo(terminal, || read_secret(&mut input))?;
+        }
+        match self.password_fallback {
+            PasswordFallback::Input => Ok(self.read_line()?.map(Secret::from)),
+            PasswordFallback::Tty => {
+                let tty = fs::File::open("/dev/tty").map_err(|_| Error::NonInteractive)?;
+                let mut input = &tty;
+                terminal::without_echo(&tty, || read_secret(&mut input))?
+            }
+            PasswordFallback::Error => Err(Error::NonInteractive),
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn read_password(&self) -> Result<Option<Secret>> {
+        if self.terminal.is_some() {
+            return Ok(Some(Secret::from(rpassword::read_password()?)));
+        }
+        match self.password_fallback {
+            PasswordFallback::Input => Ok(self.read_line()?.map(Secret::from)),
+            PasswordFallback::Tty => rpassword::read_password_from_tty(None)
+                .map(|password| Some(Secret::from(password)))
+                .map_err(|_| Error::NonInteractive),
+            PasswordFallback::Error => Err(Error::NonInteractive),
+        }
+    }
+}
+
+/// Reads a secret line from the given unbuffered reader and returns `None` at the end of the
+/// input.
+///
+/// The input is read byte by byte so that no copies of the secret remain in a read buffer, and
+/// the buffer is wiped before it is grown or dropped.
+#[cfg_attr(not(unix), allow(dead_code))]
+fn read_secret(input: &mut impl Read) -> Result<Option<Secret>> {
+    let mut buffer = Vec::with_capacity(crate::backends::SECRET_CAPACITY);
+    let mut byte = [0; 1];
+    loop {
+        let n = match input.read(&mut byte) {
+            Ok(n) => n,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => {
+                buffer.zeroize();
+                return Err(err.into());
+            }
+        };
+        if n == 0 {
+            if buffer.is_empty() {
+                return Ok(None);
+            }
+            break;
+        }
+        if byte[0] == b'\n' {
+            break;
+        }
+        if buffer.len() == buffer.capacity() {
+            let mut grown = Vec::with_capacity(buffer.capacity() * 2);
+            grown.extend_from_slice(&buffer);
+            buffer.zeroize();
+            buffer = grown;
+        }
+        buffer.push(byte[0]);
+    }
+    byte.zeroize();
+    if buffer.last() == Some(&b'\r') {
+        buffer.truncate(buffer.len() - 1);
+    }
+    match String::from_utf8(buffer) {
+        Ok(s) => Ok(Some(Secret::from(s))),
+        Err(err) => {
+            let utf8_error = err.utf8_error();
+            err.into_bytes().zeroize();
+            Err(utf8_error.into())
+        }
+    }
+}
+
+/// Copies the output of a program to the given writer as soon as it is available.
+fn copy_output(reader: &mut impl Read, writer: &mut impl Write) -> io::Result<()> {
+    let mut buffer = [0; 4096];
+    loop {
+        match reader.read(&mut buffer) {
+            Ok(0) => return Ok(()),
+            Ok(n) => {
+                writer.write_all(&buffer[..n])?;
+                writer.flush()?;
+            }
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Reads a line from the given reader and returns `None` at the end of the input.
+fn read_line(reader: &mut impl BufRead) -> io::Result<Option<String>> {
+    let mut input = String::new();
+    if reader.read_line(&mut input)? == 0 {
+        return Ok(None);
+    }
+    let len = input.trim_end_matches('\n').trim_end_matches('\r').len();
+    input.truncate(len);
+    Ok(Some(input))
+}
+
+impl Default for Stdio {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R, W> fmt::Debug for Stdio<R, W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Stdio")
+            .field("interactive", &self.terminal.is_some())
+            .field("framed", &self.framed)
+            .field("colored", &self.colored)
+            .field("password_fallback", &self.password_fallback)
+            .finish()
+    }
+}
+
+impl<R, W> AsRef<Stdio<R, W>> for Stdio<R, W> {
+    fn as_ref(&self) -> &Self {
+        self
+    }
+}
+
+const BOLD: &str = "1";
+const YELLOW: &str = "33";
+
+/// The line that finishes a multi-line text input.
+const TEXT_TERMINATOR: &str = ".";
+
+impl<R: BufRead, W: Write> super::Backend for Stdio<R, W> {
+    fn show_input(&self, input: &Input) -> Result<Option<String>> {
+        self.print_title(&input.title)?;
+        let user_input = self.read_input(input)?;
+        if let (Some(id), Some(user_input)) = (&input.history, &user_input) {
+            history::add(id, user_input);
+        }
+        Ok(user_input)
+    }
+
+    fn show_license(&self, license: &License) -> Result<bool> {
+        let text = license.source.read()?;
+        self.page(&license.title, &text)?;
+        let label = self.prompt_text(&license.label);
+        let options = self.catalog.confirmation_options();
+        loop {
+            self.print_prompt(format_args!("{} [{}]: ", label, options))?;
+            let answer = match self.read_line()? {
+                Some(answer) => answer,
+                None => return Ok(false),
+            };
+            if self.catalog.is_confirmation(&answer) {
+                return Ok(true);
+            } else if self.catalog.parse_choice(&answer) == Some(Choice::No) {
+                return Ok(false);
+            }
+            self.print_warning(self.catalog.invalid_answer())?;
+        }
+    }
+
+    fn show_form(&self, form: &Form) -> Result<Option<Vec<String>>> {
+        self.print_text(&form.title, &form.text)?;
+        let mut values = Vec::with_capacity(form.fields.len());
+        for field in &form.fields {
+            let value = match field.kind {
+                FieldKind::Text => {
+                    let mut input = Input::new(field.label.as_str());
+                    if !field.value.is_empty() {
+                        input.default(field.value.as_str());
+                    }
+                    self.read_input(&input)?
+                }
+                FieldKind::Password => {
+                    let label = self.prompt_text(&field.label);
+                    self.print_prompt(format_args!("{}: ", label))?;
+                    self.read_password()?
+                        .map(|password| password.expose().to_owned())
+                }
+                FieldKind::ReadOnly => {
+                    let label = self.prompt_text(&field.label);
+                    self.print_prompt(format_args!("{}: {}\n", label, field.value))?;
+                    Some(field.value.clone())
+                }
+            };
+            match value {
+                Some(value) => values.push(value),
+                None => return Ok(None),
+            }
+        }
+        Ok(Some(values))
+    }
+
+    fn show_login(&self, login: &Login) -> Result<Option<Credentials>> {
+        self.print_text(&login.title, &login.text)?;
+        let mut input = Input::new(login.username_label.as_str());
+        if let Some(ref username) = login.username {
+            input.default(username.as_str());
+        }
+        let username = match self.read_input(&input)? {
+            Some(username) => username,
+            None => return Ok(None),
+        };
+        let label = self.prompt_text(&login.password_label);
+        self.print_prompt(format_args!("{}: ", label))?;
+        let password = self.read_password()?;
+        Ok(password.map(|password| Credentials::new(username, password)))
+    }
+
+    fn show_menu(&self, menu: &Menu) -> Result<Option<usize>> {
+        self.print_text(&menu.title, &menu.text)?;
+        {
+            let mut writer = self.writer();
+            for (tag, label) in menu.tagged_items() {
+                writeln!(writer, "{:>3}) {}", tag, label)?;
+            }
+        }
+        loop {
+            self.print_prompt(format_args!("[1-{}]: ", menu.items.len()))?;
+            let answer = match self.read_line()? {
+                Some(answer) => answer,
+                None => return Ok(None),
+            };
+            if let Ok(index) = menu.parse_tag(&answer) {
+                return Ok(Some(index));
+            }
+            self.print_warning(self.catalog.invalid_item())?;
+        }
+    }
+
+    fn show_message(&self, message: &Message) -> Result<()> {
+        self.print_text(&message.title, &message.text)
+    }
+
+    fn show_password(&self, password: &Password) -> Result<Option<Secret>> {
+        self.print_title(&password.title)?;
+        self.print_prompt(format_args!("{}: ", self.prompt_text(&password.text)))?;
+        self.read_password()
+    }
+
+    fn show_program_box(&self, program: &ProgramBox) -> Result<process::ExitStatus> {
+        match program.text {
+            Some(ref text) => self.print_text(&program.title, text)?,
+            None => self.print_title(&program.title)?,
+        }
+        let (mut child, mut reader) = program.spawn()?;
+        let result = copy_output(&mut reader, &mut *self.writer());
+        let status = child.wait()?;
+        result?;
+        Ok(status)
+    }
+
+    fn show_question(&self, question: &Question) -> Result<Choice> {
+        self.print_title(&question.title)?;
+        let text = self.prompt_text(&question.text);
+        let options = self.catalog.options(question.default);
+        loop {
+            self.print_prompt(format_args!("{} [{}]: ", text, options))?;
+            let answer = match self.read_line()? {
+                Some(answer) => answer,
+                None => return Ok(Choice::Cancel),
+            };
+            if answer.trim().is_empty() {
+                if let Some(default) = question.default {
+                    return Ok(default);
+                }
+            } else if let Some(choice) = self.catalog.parse_choice(&answer) {
+                return Ok(choice);
+            }
+            self.print_warning(self.catalog.invalid_answer())?;
+        }
+    }
+
+    fn show_tail(&self, tail: &Tail) -> Result<()> {
+        self.print_title(&tail.title)?;
+        tail.wait_for_file();
+        let mut follower = crate::backends::Follower::open(&tail.path)?;
+        if self.terminal.is_some() {
+            self.print_warning(self.catalog.tail_hint())?;
+        } else if tail.completion.is_none() {
+            // There is no way to stop following the file, so just print the current content
+            self.writer().write_all(&follower.read_new()?)?;
+            return Ok(());
+        }
+
+        loop {
+            let is_complete = tail.is_complete();
+            {
+                let mut writer = self.writer();
+                writer.write_all(&follower.read_new()?)?;
+                writer.flush()?;
+            }
+            if is_complete {
+                return Ok(());
+            }
+            match self.terminal {
+                Some(ref terminal) => {
+                    if terminal::wait_for_input(terminal, crate::backends::POLL_INTERVAL)? {
+                        self.read_line()?;
+                        return Ok(());
+                    }
+                }
+                None => thread::sleep(crate::backends::POLL_INTERVAL),
+            }
+        }
+    }
+
+    fn show_text_input(&self, input: &TextInput) -> Result<Option<String>> {
+        self.print_text(&input.title, &input.text)?;
+        if let Some(ref default) = input.default {
+            let mut writer = self.writer();
+            writeln!(writer, "[{}]", self.catalog.default_label())?;
+            for line in default.lines() {
+                writeln!(writer, "  {}", line)?;
+            }
+        }
+        self.print_text(&None, self.catalog.text_input_hint())?;
+
+        let mut lines = Vec::new();
+        loop {
+            match self.read_line()? {
+                Some(line) if line == TEXT_TERMINATOR => break,
+                Some(line) => lines.push(line),
+                None if lines.is_empty() => return Ok(None),
+                None => break,
+            }
+        }
+        if lines.is_empty() {
+            if let Some(ref default) = input.default {
+                return Ok(Some(crate::backends::trim_text(default.clone())));
+            }
+        }
+        Ok(Some(crate::backends::trim_text(lines.join("\n"))))
+    }
+
+    fn show_text_viewer(&self, viewer: &TextViewer) -> Result<()> {
+        let text = viewer.source.read()?;
+        self.page(&viewer.title, &text)
+    }
+}
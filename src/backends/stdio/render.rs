@@ -0,0 +1,248 @@
+// Copyright (C) 2019 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: MIT
+
+//! Helper functions to lay out the text printed by the `Stdio` backend.
+
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Returns the number of columns needed to display the given string.
+pub(crate) fn width(s: &str) -> usize {
+    UnicodeWidthStr::width(s)
+}
+
+/// The number of columns between tab stops.
+const TAB_WIDTH: usize = 8;
+
+/// Wraps the given text so that no line is wider than the given number of columns.
+///
+/// Line breaks and whitespace in the text are preserved, except at the points where a line is
+/// wrapped.  Tabs are expanded to spaces.  Continuation lines are indented like the first line of
+/// the paragraph.  Words that are wider than a line are split.
+pub(crate) fn wrap(text: &str, max_width: usize) -> Vec<String> {
+    let max_width = max_width.max(1);
+    let mut lines = Vec::new();
+    for paragraph in text.split('\n') {
+        let paragraph = expand_tabs(paragraph);
+        if width(&paragraph) <= max_width {
+            lines.push(paragraph);
+            continue;
+        }
+
+        let content = paragraph.trim_start();
+        let mut indent = &paragraph[..paragraph.len() - content.len()];
+        if width(indent) >= max_width {
+            indent = "";
+        }
+        let indent_width = width(indent);
+
+        let mut line = indent.to_owned();
+        let mut line_width = indent_width;
+        let mut at_start = true;
+        for (space, word) in segments(content) {
+            let word_width = width(word);
+            if !at_start && line_width + width(space) + word_width > max_width {
+                lines.push(std::mem::replace(&mut line, indent.to_owned()));
+                line_width = indent_width;
+                at_start = true;
+            }
+            if !at_start {
+                line.push_str(space);
+                line_width += width(space);
+            }
+            if line_width + word_width > max_width {
+                for c in word.chars() {
+                    let char_width = c.width().unwrap_or(0);
+                    if line_width > indent_width && line_width + char_width > max_width {
+                        lines.push(std::mem::replace(&mut line, indent.to_owned()));
+                        line_width = indent_width;
+                    }
+                    line.push(c);
+                    line_width += char_width;
+                }
+            } else {
+                line.push_str(word);
+                line_width += word_width;
+            }
+            at_start = false;
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Replaces the tabs in the given line with spaces up to the next tab stop.
+fn expand_tabs(line: &str) -> String {
+    let mut expanded = String::with_capacity(line.len());
+    let mut column = 0;
+    for c in line.chars() {
+        if c == '\t' {
+            let n = TAB_WIDTH - column % TAB_WIDTH;
+            expanded.extend(std::iter::repeat_n(' ', n));
+            column += n;
+        } else {
+            expanded.push(c);
+            column += c.width().unwrap_or(0);
+        }
+    }
+    expanded
+}
+
+/// Splits the given text into words and the whitespace preceding them.
+///
+/// Trailing whitespace is returned as a segment with an empty word.
+fn segments(text: &str) -> Vec<(&str, &str)> {
+    let mut segments = Vec::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        let word_start = rest
+            .find(|c: char| !c.is_whitespace())
+            .unwrap_or(rest.len());
+        let word_end = rest[word_start..]
+            .find(char::is_whitespace)
+            .map(|i| word_start + i)
+            .unwrap_or(rest.len());
+        segments.push((&rest[..word_start], &rest[word_start..word_end]));
+        rest = &rest[word_end..];
+    }
+    segments
+}
+
+/// Shortens the given text to the given number of columns, replacing the end with an ellipsis.
+pub(crate) fn truncate(text: &str, max_width: usize) -> String {
+    if width(text) <= max_width {
+        return text.to_owned();
+    }
+    let mut truncated = String::new();
+    let mut truncated_width = 0;
+    for c in text.chars() {
+        let char_width = c.width().unwrap_or(0);
+        if truncated_width + char_width + 1 > max_width {
+            break;
+        }
+        truncated.push(c);
+        truncated_width += char_width;
+    }
+    if max_width > 0 {
+        truncated.push('…');
+    }
+    truncated
+}
+
+/// Draws a frame around the given lines with an optional title in the top border.
+///
+/// The frame is widened if the title is wider than the lines, so the title should be truncated to
+/// the available width first.  The title is passed through the `paint` function after the layout
+/// has been computed so that escape sequences do not affect the width calculation.
+pub(crate) fn frame(
+    title: Option<&str>,
+    lines: &[String],
+    paint: impl Fn(&str) -> String,
+) -> Vec<String> {
+    let title_width = title.map(|title| width(title) + 2).unwrap_or(0);
+    let inner = lines
+        .iter()
+        .map(|line| width(line))
+        .max()
+        .unwrap_or(0)
+        .max(title_width);
+
+    let mut framed = Vec::with_capacity(lines.len() + 2);
+    framed.push(match title {
+        Some(title) => format!(
+            "┌─ {} {}┐",
+            paint(title),
+            "─".repeat(inner + 1 - title_width)
+        ),
+        None => format!("┌{}┐", "─".repeat(inner + 2)),
+    });
+    for line in lines {
+        framed.push(format!("│ {}{} │", line, " ".repeat(inner - width(line))));
+    }
+    framed.push(format!("└{}┘", "─".repeat(inner + 2)));
+    framed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{frame, truncate, width, wrap};
+
+    fn wrap_lines(text: &str, max_width: usize) -> Vec<String> {
+        let lines = wrap(text, max_width);
+        for line in &lines {
+            assert!(width(line) <= max_width, "line too wide: {:?}", line);
+        }
+        lines
+    }
+
+    #[test]
+    fn wrap_words() {
+        assert_eq!(
+            vec!["lorem ipsum", "dolor sit", "amet"],
+            wrap_lines("lorem ipsum dolor sit amet", 11)
+        );
+        assert_eq!(vec!["short"], wrap_lines("short", 11));
+        assert_eq!(vec!["a", "", "b"], wrap_lines("a\n\nb", 11));
+    }
+
+    #[test]
+    fn wrap_indent() {
+        assert_eq!(
+            vec!["  lorem", "  ipsum", "  dolor"],
+            wrap_lines("  lorem ipsum dolor", 8)
+        );
+        assert_eq!(vec!["        a b"], wrap_lines("\ta b", 11));
+    }
+
+    #[test]
+    fn wrap_long_words() {
+        assert_eq!(vec!["abcde", "fghij", "k"], wrap_lines("abcdefghijk", 5));
+        assert_eq!(vec!["a", "bcdef", "ghi"], wrap_lines("a bcdefghi", 5));
+    }
+
+    #[test]
+    fn wrap_wide_characters() {
+        // Each of these characters takes two columns
+        assert_eq!(vec!["日本", "語"], wrap_lines("日本語", 5));
+        assert_eq!(vec!["日本", "語 の", "本"], wrap_lines("日本語 の 本", 5));
+    }
+
+    #[test]
+    fn wrap_combining_characters() {
+        // e followed by a combining acute accent takes one column
+        let word = "e\u{301}e\u{301}e\u{301}";
+        assert_eq!(3, width(word));
+        assert_eq!(
+            vec![word, word],
+            wrap_lines(&format!("{} {}", word, word), 5)
+        );
+    }
+
+    #[test]
+    fn truncate_text() {
+        assert_eq!("title", truncate("title", 5));
+        assert_eq!("tit…", truncate("title", 4));
+        assert_eq!("日…", truncate("日本語", 4));
+        assert_eq!("…", truncate("title", 1));
+        assert_eq!("", truncate("title", 0));
+    }
+
+    #[test]
+    fn frame_lines() {
+        let lines = vec!["ab".to_string(), "abcd".to_string()];
+        assert_eq!(
+            vec!["┌──────┐", "│ ab   │", "│ abcd │", "└──────┘"],
+            frame(None, &lines, |s| s.to_string())
+        );
+        assert_eq!(
+            vec!["┌─ T ──┐", "│ ab   │", "│ abcd │", "└──────┘"],
+            frame(Some("T"), &lines, |s| s.to_string())
+        );
+    }
+
+    #[test]
+    fn frame_wide_title() {
+        let lines = vec!["ab".to_string()];
+        let framed = frame(Some("Title"), &lines, |s| format!("*{}*", s));
+        assert_eq!(vec!["┌─ *Title* ─┐", "│ ab      │", "└─────────┘"], framed);
+    }
+}
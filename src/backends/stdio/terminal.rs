@@ -0,0 +1,132 @@
+// Copyright (C) 2019 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: MIT
+
+//! Helper functions to query and configure the terminal used by the `Stdio` backend.
+
+use std::env;
+use std::fs;
+use std::io;
+use std::time;
+
+/// Returns a handle for the given standard stream that can be used to configure the terminal.
+#[cfg(unix)]
+pub(crate) fn handle(stream: &impl std::os::unix::io::AsFd) -> Option<fs::File> {
+    stream.as_fd().try_clone_to_owned().ok().map(fs::File::from)
+}
+
+#[cfg(windows)]
+pub(crate) fn handle(stream: &impl std::os::windows::io::AsHandle) -> Option<fs::File> {
+    stream
+        .as_handle()
+        .try_clone_to_owned()
+        .ok()
+        .map(fs::File::from)
+}
+
+#[cfg(not(any(unix, windows)))]
+pub(crate) fn handle<T>(_stream: &T) -> Option<fs::File> {
+    None
+}
+
+/// Returns the width of the given terminal in columns.
+///
+/// If the width cannot be queried from the terminal, the `COLUMNS` environment variable is used.
+pub(crate) fn width(terminal: &fs::File) -> Option<usize> {
+    query_size(terminal)
+        .map(|(_, columns)| columns)
+        .or_else(|| from_env("COLUMNS"))
+}
+
+/// Returns the height of the given terminal in lines.
+///
+/// If the height cannot be queried from the terminal, the `LINES` environment variable is used.
+pub(crate) fn height(terminal: &fs::File) -> Option<usize> {
+    query_size(terminal)
+        .map(|(lines, _)| lines)
+        .or_else(|| from_env("LINES"))
+}
+
+fn from_env(name: &str) -> Option<usize> {
+    env::var(name)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .filter(|&value| value > 0)
+}
+
+/// Returns the number of lines and columns of the given terminal.
+#[cfg(unix)]
+fn query_size(terminal: &fs::File) -> Option<(usize, usize)> {
+    use std::mem;
+    use std::os::unix::io::AsRawFd;
+
+    // SAFETY: winsize is a plain C struct that is fully initialized by a successful ioctl call.
+    let mut size: libc::winsize = unsafe { mem::zeroed() };
+    let result = unsafe { libc::ioctl(terminal.as_raw_fd(), libc::TIOCGWINSZ, &mut size) };
+    if result == 0 && size.ws_row > 0 && size.ws_col > 0 {
+        Some((usize::from(size.ws_row), usize::from(size.ws_col)))
+    } else {
+        None
+    }
+}
+
+#[cfg(not(unix))]
+fn query_size(_terminal: &fs::File) -> Option<(usize, usize)> {
+    None
+}
+
+/// Waits until input is available on the given terminal or the timeout expires.
+///
+/// Returns `true` if input is available.
+#[cfg(unix)]
+pub(crate) fn wait_for_input(terminal: &fs::File, timeout: time::Duration) -> io::Result<bool> {
+    use std::convert::TryFrom;
+    use std::os::unix::io::AsRawFd;
+
+    let mut fd = libc::pollfd {
+        fd: terminal.as_raw_fd(),
+        events: libc::POLLIN,
+        revents: 0,
+    };
+    let timeout = libc::c_int::try_from(timeout.as_millis()).unwrap_or(libc::c_int::MAX);
+    // SAFETY: fd is a valid pollfd struct and the number of entries is one.
+    match unsafe { libc::poll(&mut fd, 1, timeout) } {
+        -1 if io::Error::last_os_error().kind() == io::ErrorKind::Interrupted => Ok(false),
+        -1 => Err(io::Error::last_os_error()),
+        n => Ok(n > 0),
+    }
+}
+
+#[cfg(not(unix))]
+pub(crate) fn wait_for_input(_terminal: &fs::File, timeout: time::Duration) -> io::Result<bool> {
+    std::thread::sleep(timeout);
+    Ok(false)
+}
+
+/// Executes the given function while the echo of the given terminal is disabled.
+#[cfg(unix)]
+pub(crate) fn without_echo<T>(terminal: &fs::File, f: impl FnOnce() -> T) -> crate::Result<T> {
+    use std::io;
+    use std::mem;
+    use std::os::unix::io::AsRawFd;
+
+    let fd = terminal.as_raw_fd();
+    // SAFETY: termios is a plain C struct that is fully initialized by tcgetattr.
+    let mut term: libc::termios = unsafe { mem::zeroed() };
+    if unsafe { libc::tcgetattr(fd, &mut term) } != 0 {
+        return Err(io::Error::last_os_error().into());
+    }
+    let orig = term;
+    // Hide the input, but still echo the newline when the user presses Enter
+    term.c_lflag &= !libc::ECHO;
+    term.c_lflag |= libc::ECHONL;
+    if unsafe { libc::tcsetattr(fd, libc::TCSANOW, &term) } != 0 {
+        return Err(io::Error::last_os_error().into());
+    }
+
+    let result = f();
+
+    if unsafe { libc::tcsetattr(fd, libc::TCSANOW, &orig) } != 0 {
+        return Err(io::Error::last_os_error().into());
+    }
+    Ok(result)
+}
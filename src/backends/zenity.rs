@@ -1,16 +1,130 @@
 // Copyright (C) 2019 Robin Krahl <robin.krahl@ireas.org>
 // SPDX-License-Identifier: MIT
 
+use std::collections::HashMap;
+use std::ffi;
+use std::fmt;
+use std::io::{self, Write};
+use std::path;
 use std::process;
+use std::sync::{Mutex, OnceLock};
 
-use crate::{Choice, Error, Input, Message, Password, Question, Result};
+use crate::{
+    Choice, Credentials, Error, FieldKind, Form, Input, License, Login, Menu, Message, Password,
+    ProgramBox, Question, Result, Secret, Tail, TextInput, TextViewer,
+};
+
+/// The names of the programs that are compatible with `zenity`, in order of preference.
+pub(crate) const PROGRAMS: [&str; 3] = ["zenity", "qarma", "matedialog"];
+
+/// The `zenity` implementation used by a backend instance.
+///
+/// `qarma` and `matedialog` accept the same command-line interface as `zenity` with some minor
+/// differences:
+/// - `qarma` does not support the `--window-icon` option, so the icon setting is ignored.
+/// - `qarma` exits with status 255 if the dialog window is closed, which is handled like a cancel
+///   event.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Flavor {
+    Zenity,
+    Qarma,
+    MateDialog,
+}
+
+impl Flavor {
+    fn from_program(program: &path::Path) -> Flavor {
+        match program.file_stem().and_then(|name| name.to_str()) {
+            Some("qarma") => Flavor::Qarma,
+            Some("matedialog") => Flavor::MateDialog,
+            _ => Flavor::Zenity,
+        }
+    }
+
+    fn supports_window_icon(self) -> bool {
+        self != Flavor::Qarma
+    }
+
+    fn is_cancel(self, code: i32) -> bool {
+        match code {
+            1 | 5 => true,
+            255 => self == Flavor::Qarma,
+            _ => false,
+        }
+    }
+}
+
+/// A `zenity` version number.
+///
+/// The version of the `zenity` program used by a backend can be queried with
+/// [`Zenity::version`][].
+///
+/// [`Zenity::version`]: struct.Zenity.html#method.version
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct ZenityVersion {
+    major: u32,
+    minor: u32,
+    patch: u32,
+}
+
+impl ZenityVersion {
+    /// Returns the major version number.
+    pub fn major(&self) -> u32 {
+        self.major
+    }
+
+    /// Returns the minor version number.
+    pub fn minor(&self) -> u32 {
+        self.minor
+    }
+
+    /// Returns the patch version number.
+    pub fn patch(&self) -> u32 {
+        self.patch
+    }
+
+    fn parse(s: &str) -> Option<ZenityVersion> {
+        let mut parts = s.trim().split('.').map(|part| part.parse::<u32>());
+        let major = parts.next()?.ok()?;
+        let minor = parts.next().unwrap_or(Ok(0)).ok()?;
+        let patch = parts.next().unwrap_or(Ok(0)).ok()?;
+        Some(ZenityVersion {
+            major,
+            minor,
+            patch,
+        })
+    }
+}
+
+impl fmt::Display for ZenityVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
 
 /// The `zenity` backend.
 ///
-/// This backend uses the external `zenity` program to display GTK+ dialog boxes.
+/// This backend uses the external `zenity` program to display GTK+ dialog boxes.  It can also be
+/// used with the `zenity` clones `qarma` (Qt) and `matedialog` (MATE), see
+/// [`with_program`][].
+///
+/// `zenity` 4 removed some options that were supported by `zenity` 3.  The backend detects the
+/// version of `zenity` before showing the first dialog box and adapts the arguments accordingly,
+/// see [`version`][]:
+/// - `--window-icon` has been removed, so the icon is passed with `--icon` for message and
+///   question dialogs and ignored for all other dialogs.
+/// - `--attach` has been removed, so the parent window set with [`set_attach`][] is ignored.
+///
+/// `zenity` 4 still accepts `--width` and `--height`, but treats them as the minimum size of the
+/// dialog box, and some dialog boxes ignore them.  The backend passes them unchanged.
+///
+/// [`set_attach`]: #method.set_attach
+/// [`with_program`]: #method.with_program
+/// [`version`]: #method.version
 #[derive(Debug)]
 pub struct Zenity {
+    program: super::Program,
     icon: Option<String>,
+    attach: Option<String>,
     width: Option<String>,
     height: Option<String>,
     timeout: Option<String>,
@@ -20,13 +134,37 @@ impl Zenity {
     /// Creates a new `Zenity` instance without configuration.
     pub fn new() -> Zenity {
         Zenity {
+            program: super::Program::new("zenity"),
             icon: None,
+            attach: None,
             width: None,
             height: None,
             timeout: None,
         }
     }
 
+    /// Creates a new `Zenity` instance that uses the given `zenity`-compatible program.
+    ///
+    /// The program can be either `zenity` or one of its clones `qarma` and `matedialog`, given
+    /// as a name to look up in the `PATH` or as a path to the executable.  The known differences
+    /// between the clones are detected using the file name of the program.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use dialog::DialogBox;
+    ///
+    /// let backend = dialog::backends::Zenity::with_program("qarma");
+    /// dialog::Message::new("Hello from Qt!")
+    ///     .show_with(&backend)
+    ///     .expect("Could not display dialog box");
+    /// ```
+    pub fn with_program(program: impl Into<path::PathBuf>) -> Zenity {
+        let mut zenity = Zenity::new();
+        zenity.set_program(program);
+        zenity
+    }
+
     /// Sets the icon of the dialog box.
     ///
     /// The icon can either be one of `error`, `info`, `question` or `warning, or the path to an
@@ -35,6 +173,14 @@ impl Zenity {
         self.icon = Some(icon.into());
     }
 
+    /// Sets the parent window of the dialog boxes.
+    ///
+    /// The window is identified by its X11 window ID.  This setting is ignored for `zenity` 4,
+    /// which removed the `--attach` option.
+    pub fn set_attach(&mut self, window_id: u64) {
+        self.attach = Some(window_id.to_string());
+    }
+
     /// Sets the height of the dialog boxes.
     ///
     /// The height is given in pixels.  The actual height of the dialog box might be higher than
@@ -59,16 +205,106 @@ impl Zenity {
         self.timeout = Some(timeout.to_string());
     }
 
-    pub(crate) fn is_available() -> bool {
-        super::is_available("zenity")
+    /// Sets the path of the `zenity` executable.
+    ///
+    /// Per default, `zenity` is looked up in the directories listed in the `PATH` environment
+    /// variable.
+    pub fn set_program(&mut self, program: impl Into<path::PathBuf>) {
+        self.program.set_path(program);
+    }
+
+    /// Sets a command prefix that is used to run the `zenity` executable.
+    ///
+    /// The first element is the program to execute, the remaining elements are passed to it as
+    /// arguments, followed by the `zenity` executable and its arguments.  For example, the
+    /// wrapper `["flatpak-spawn", "--host"]` runs `zenity` on the host system from within a
+    /// Flatpak sandbox.
+    pub fn set_wrapper<I, S>(&mut self, wrapper: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<ffi::OsString>,
+    {
+        self.program.set_wrapper(wrapper);
+    }
+
+    /// Sets an environment variable for the `zenity` process.
+    ///
+    /// This method can be called multiple times to set multiple variables.  If a wrapper is set,
+    /// the variable is passed to `flatpak-spawn` with the `--env` option.  For other wrappers, it
+    /// is set for the wrapper process.
+    pub fn set_env(&mut self, key: impl Into<ffi::OsString>, value: impl Into<ffi::OsString>) {
+        self.program.set_env(key, value);
+    }
+
+    /// Sets the working directory of the `zenity` process.
+    ///
+    /// If a wrapper is set, the directory is passed to `flatpak-spawn` with the `--directory`
+    /// option.  For other wrappers, it is set for the wrapper process.
+    pub fn set_current_dir(&mut self, dir: impl Into<path::PathBuf>) {
+        self.program.set_current_dir(dir);
+    }
+
+    /// Returns the version of the `zenity` program used by this backend.
+    ///
+    /// The version is determined by running `zenity --version` once per program and process.
+    /// This method returns `None` if the version could not be determined or if this backend uses
+    /// one of the `zenity` clones `qarma` or `matedialog`.
+    pub fn version(&self) -> Option<ZenityVersion> {
+        static CACHE: OnceLock<Mutex<HashMap<super::Program, Option<ZenityVersion>>>> =
+            OnceLock::new();
+
+        if self.flavor() != Flavor::Zenity {
+            return None;
+        }
+
+        let cache = CACHE.get_or_init(Default::default);
+        let lock = || cache.lock().unwrap_or_else(|err| err.into_inner());
+        if let Some(version) = lock().get(&self.program) {
+            return *version;
+        }
+        // Do not hold the lock while running zenity.  If multiple threads probe the version at the
+        // same time, the first result is kept.
+        let version = probe_version(&self.program);
+        *lock().entry(self.program.clone()).or_insert(version)
+    }
+
+    /// Creates a new instance for the first available program of `zenity` and its clones.
+    pub(crate) fn detect() -> Option<Zenity> {
+        PROGRAMS
+            .iter()
+            .find(|program| super::is_available(program))
+            .map(|program| Zenity::with_program(*program))
+    }
+
+    fn flavor(&self) -> Flavor {
+        Flavor::from_program(self.program.path())
     }
 
     fn execute(&self, args: Vec<&str>, title: &Option<String>) -> Result<process::Output> {
-        let mut command = process::Command::new("zenity");
+        self.command(args, title).output().map_err(Error::IoError)
+    }
+
+    fn command(&self, args: Vec<&str>, title: &Option<String>) -> process::Command {
+        let mut command = self.program.command();
+        let is_zenity4 = self.version().is_some_and(|version| version.major() >= 4);
 
         if let Some(ref icon) = self.icon {
-            command.arg("--window-icon");
-            command.arg(icon);
+            if is_zenity4 {
+                // zenity 4 removed --window-icon, but supports --icon for message dialogs
+                if args.first().is_some_and(|arg| is_message_dialog(arg)) {
+                    command.arg("--icon");
+                    command.arg(icon);
+                }
+            } else if self.flavor().supports_window_icon() {
+                command.arg("--window-icon");
+                command.arg(icon);
+            }
+        }
+        if let Some(ref attach) = self.attach {
+            if !is_zenity4 {
+                command.arg("--attach");
+                command.arg(attach);
+            }
         }
         if let Some(ref width) = self.width {
             command.arg("--width");
@@ -88,7 +324,13 @@ impl Zenity {
         }
 
         command.args(args);
-        command.output().map_err(Error::IoError)
+        command
+    }
+}
+
+impl Default for Zenity {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -98,25 +340,54 @@ impl AsRef<Zenity> for Zenity {
     }
 }
 
-fn require_success(status: process::ExitStatus) -> Result<()> {
+fn probe_version(program: &super::Program) -> Option<ZenityVersion> {
+    let output = program.command().arg("--version").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    stdout.lines().next().and_then(ZenityVersion::parse)
+}
+
+fn is_message_dialog(arg: &str) -> bool {
+    matches!(arg, "--error" | "--info" | "--question" | "--warning")
+}
+
+fn get_secret(flavor: Flavor, mut output: process::Output) -> Result<Option<Secret>> {
+    let secret = super::take_secret(&mut output)?;
+    if output.status.success() {
+        Ok(Some(secret))
+    } else if let Some(code) = output.status.code() {
+        if flavor.is_cancel(code) {
+            Ok(None)
+        } else {
+            Err(Error::from(("zenity", output.status)))
+        }
+    } else {
+        Err(Error::from(("zenity", output.status)))
+    }
+}
+
+fn require_success(flavor: Flavor, status: process::ExitStatus) -> Result<()> {
     if status.success() {
         Ok(())
     } else if let Some(code) = status.code() {
-        match code {
-            5 => Ok(()),
-            _ => Err(Error::from(("zenity", status))),
+        if flavor.is_cancel(code) {
+            Ok(())
+        } else {
+            Err(Error::from(("zenity", status)))
         }
     } else {
         Err(Error::from(("zenity", status)))
     }
 }
 
-fn get_choice(status: process::ExitStatus) -> Result<Choice> {
+fn get_choice(flavor: Flavor, status: process::ExitStatus) -> Result<Choice> {
     if let Some(code) = status.code() {
         match code {
             0 => Ok(Choice::Yes),
             1 => Ok(Choice::No),
-            5 => Ok(Choice::Cancel),
+            code if flavor.is_cancel(code) => Ok(Choice::Cancel),
             _ => Err(Error::from(("zenity", status))),
         }
     } else {
@@ -124,48 +395,231 @@ fn get_choice(status: process::ExitStatus) -> Result<Choice> {
     }
 }
 
-fn get_stdout(output: process::Output) -> Result<Option<String>> {
+fn get_stdout(flavor: Flavor, output: process::Output) -> Result<Option<String>> {
     if output.status.success() {
         String::from_utf8(output.stdout)
             .map(|s| Some(s.trim_end_matches('\n').to_string()))
             .map_err(Error::from)
     } else if let Some(code) = output.status.code() {
-        match code {
-            0 => Ok(None),
-            1 => Ok(None),
-            5 => Ok(None),
-            _ => Err(Error::from(("zenity", output.status))),
+        if flavor.is_cancel(code) {
+            Ok(None)
+        } else {
+            Err(Error::from(("zenity", output.status)))
         }
     } else {
         Err(Error::from(("zenity", output.status)))
     }
 }
 
+/// The separator between the values of a `--forms` dialog.
+const FORM_SEPARATOR: char = '\u{1f}';
+
 impl super::Backend for Zenity {
+    fn show_form(&self, form: &Form) -> Result<Option<Vec<String>>> {
+        // zenity does not support default values or read-only fields, so we add the read-only
+        // fields to the text
+        let mut text = form.text.clone();
+        let mut args = vec!["--forms".to_string()];
+        for field in &form.fields {
+            match field.kind {
+                FieldKind::Text => args.push(format!("--add-entry={}", field.label)),
+                FieldKind::Password => args.push(format!("--add-password={}", field.label)),
+                FieldKind::ReadOnly => {
+                    text.push_str(&format!("\n{}: {}", field.label, field.value))
+                }
+            }
+        }
+        args.push(format!("--text={}", text));
+        args.push(format!("--separator={}", FORM_SEPARATOR));
+        let args = args.iter().map(String::as_str).collect();
+        let output = self
+            .execute(args, &form.title)
+            .and_then(|output| get_stdout(self.flavor(), output))?;
+        output
+            .map(|output| {
+                let values = output
+                    .split(FORM_SEPARATOR)
+                    .map(ToOwned::to_owned)
+                    .collect();
+                form.merge_values(values)
+            })
+            .transpose()
+    }
+
     fn show_input(&self, input: &Input) -> Result<Option<String>> {
         let mut args = vec!["--entry", "--text", &input.text];
         if let Some(ref default) = input.default {
             args.push("--entry-text");
             args.push(default);
         }
-        self.execute(args, &input.title).and_then(get_stdout)
+        self.execute(args, &input.title)
+            .and_then(|output| get_stdout(self.flavor(), output))
+    }
+
+    fn show_license(&self, license: &License) -> Result<bool> {
+        let file = super::TextFile::new(&license.source)?;
+        let filename = format!("--filename={}", file.path().display());
+        let checkbox = format!("--checkbox={}", license.label);
+        let args = vec!["--text-info", &filename, &checkbox];
+        self.execute(args, &license.title)
+            .and_then(|output| get_choice(self.flavor(), output.status))
+            .map(|choice| choice == Choice::Yes)
+    }
+
+    fn show_login(&self, login: &Login) -> Result<Option<Credentials>> {
+        // zenity prints the username and the password separated by a pipe character
+        let args = vec!["--password", "--username"];
+        let secret = super::capture_secret(self.command(args, &login.title))
+            .and_then(|output| get_secret(self.flavor(), output))?;
+        Ok(secret.map(|secret| super::split_credentials(secret, '|')))
+    }
+
+    fn show_menu(&self, menu: &Menu) -> Result<Option<usize>> {
+        // The first column contains the tags that are printed instead of the labels
+        let text = format!("--text={}", menu.text);
+        let items = menu.tagged_items();
+        let mut args = vec![
+            "--list",
+            &text,
+            "--column=Tag",
+            "--column=Item",
+            "--hide-column=1",
+            "--print-column=1",
+            "--hide-header",
+        ];
+        for (tag, label) in &items {
+            args.push(tag);
+            args.push(label);
+        }
+        let tag = self
+            .execute(args, &menu.title)
+            .and_then(|output| get_stdout(self.flavor(), output))?;
+        // zenity returns an empty string if no item is selected
+        tag.filter(|tag| !tag.is_empty())
+            .map(|tag| menu.parse_tag(&tag))
+            .transpose()
     }
 
     fn show_message(&self, message: &Message) -> Result<()> {
         let args = vec!["--info", "--text", &message.text];
         self.execute(args, &message.title)
-            .and_then(|output| require_success(output.status))
+            .and_then(|output| require_success(self.flavor(), output.status))
             .map(|_| ())
     }
 
-    fn show_password(&self, password: &Password) -> Result<Option<String>> {
+    fn show_password(&self, password: &Password) -> Result<Option<Secret>> {
         let args = vec!["--password"];
-        self.execute(args, &password.title).and_then(get_stdout)
+        super::capture_secret(self.command(args, &password.title))
+            .and_then(|output| get_secret(self.flavor(), output))
+    }
+
+    fn show_program_box(&self, program: &ProgramBox) -> Result<process::ExitStatus> {
+        let (mut child, reader) = program.spawn()?;
+        let args = vec!["--text-info", "--auto-scroll"];
+        let mut command = self.command(args, &program.title);
+        command.stdin(reader);
+        command.stdout(process::Stdio::null());
+        let status = command.status();
+        // The command keeps the read end of the pipe open, so the program would block forever on
+        // a full pipe if zenity exits early
+        drop(command);
+        let program_status = child.wait()?;
+        get_choice(self.flavor(), status?)?;
+        Ok(program_status)
     }
 
     fn show_question(&self, question: &Question) -> Result<Choice> {
-        let args = vec!["--question", "--text", &question.text];
+        let mut args = vec!["--question", "--text", &question.text];
+        if question.default == Some(Choice::No) {
+            args.push("--default-cancel");
+        }
         self.execute(args, &question.title)
-            .and_then(|output| get_choice(output.status))
+            .and_then(|output| get_choice(self.flavor(), output.status))
+    }
+
+    fn show_tail(&self, tail: &Tail) -> Result<()> {
+        tail.wait_for_file();
+        let mut follower = super::Follower::open(&tail.path)?;
+        let args = vec!["--text-info", "--auto-scroll"];
+        let mut command = self.command(args, &tail.title);
+        command.stdin(process::Stdio::piped());
+        command.stdout(process::Stdio::null());
+        let mut child = command.spawn()?;
+        let mut stdin = child.stdin.take();
+        let status = super::wait_for_tail(&mut child, tail, || {
+            let data = follower.read_new()?;
+            if let (Some(input), false) = (&mut stdin, data.is_empty()) {
+                match input.write_all(&data) {
+                    // zenity has been closed and will be reaped by wait_for_tail
+                    Err(err) if err.kind() == io::ErrorKind::BrokenPipe => stdin = None,
+                    result => result?,
+                }
+            }
+            Ok(())
+        })?;
+        match status {
+            Some(status) => get_choice(self.flavor(), status).map(|_| ()),
+            None => Ok(()),
+        }
+    }
+
+    fn show_text_input(&self, input: &TextInput) -> Result<Option<String>> {
+        let file = super::TempFile::new(input.default.as_deref().unwrap_or(""))?;
+        let filename = format!("--filename={}", file.path().display());
+        let args = vec!["--text-info", "--editable", &filename];
+        self.execute(args, &input.title)
+            .and_then(|output| get_stdout(self.flavor(), output))
+            .map(|text| text.map(super::trim_text))
+    }
+
+    fn show_text_viewer(&self, viewer: &TextViewer) -> Result<()> {
+        let file = super::TextFile::new(&viewer.source)?;
+        let filename = format!("--filename={}", file.path().display());
+        let args = vec!["--text-info", &filename];
+        self.execute(args, &viewer.title)
+            .and_then(|output| get_choice(self.flavor(), output.status))
+            .map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ZenityVersion;
+
+    fn version(major: u32, minor: u32, patch: u32) -> Option<ZenityVersion> {
+        Some(ZenityVersion {
+            major,
+            minor,
+            patch,
+        })
+    }
+
+    #[test]
+    fn parse_version() {
+        assert_eq!(version(3, 44, 0), ZenityVersion::parse("3.44.0"));
+        assert_eq!(version(4, 0, 1), ZenityVersion::parse("4.0.1"));
+        assert_eq!(version(4, 0, 0), ZenityVersion::parse("4"));
+        assert_eq!(version(3, 32, 0), ZenityVersion::parse("3.32"));
+    }
+
+    #[test]
+    fn parse_version_whitespace() {
+        assert_eq!(version(4, 0, 1), ZenityVersion::parse("4.0.1\n"));
+        assert_eq!(version(3, 44, 0), ZenityVersion::parse("  3.44.0\r\n"));
+    }
+
+    #[test]
+    fn parse_version_garbage() {
+        assert_eq!(None, ZenityVersion::parse(""));
+        assert_eq!(None, ZenityVersion::parse("zenity"));
+        assert_eq!(None, ZenityVersion::parse("4.x"));
+        assert_eq!(None, ZenityVersion::parse("4.0.1-beta"));
+        assert_eq!(None, ZenityVersion::parse("-1.0.0"));
+    }
+
+    #[test]
+    fn version_order() {
+        assert!(ZenityVersion::parse("3.44.0") < ZenityVersion::parse("4.0.1"));
+        assert_eq!("4.0.1", ZenityVersion::parse("4.0.1").unwrap().to_string());
     }
 }
@@ -20,6 +20,8 @@ pub enum Error {
     IoError(io::Error),
     /// An UTF-8 error.
     Utf8Error(str::Utf8Error),
+    /// The dialog requires an interactive terminal, but none is available.
+    NonInteractive,
 }
 
 impl fmt::Display for Error {
@@ -28,6 +30,7 @@ impl fmt::Display for Error {
             Error::Error(ref s) => write!(f, "Error: {}", s),
             Error::IoError(ref e) => write!(f, "I/O error: {}", e),
             Error::Utf8Error(ref e) => write!(f, "UTF-8 error: {}", e),
+            Error::NonInteractive => write!(f, "No interactive terminal available"),
         }
     }
 }
@@ -7,18 +7,28 @@
 //!
 //! The `dialog` crate can be used to display different types of dialog boxes.  The supported types
 //! are:
-//! - [`Input`][]: a text input dialog
+//! - [`Form`][]: a dialog with multiple text, password and read-only fields
+//! - [`Input`][]: a text input dialog, and [`TypedInput`][] that parses the input
+//! - [`License`][]: a license agreement that has to be accepted
+//! - [`Login`][]: a username and password input dialog
+//! - [`Menu`][]: a menu to choose one of several items
 //! - [`Message`][]: a simple message box
 //! - [`Password`][]: a password input dialog
+//! - [`ProgramBox`][]: a dialog that runs a command and displays its output
 //! - [`Question`][]: a question dialog box
+//! - [`Tail`][]: a viewer that follows a growing file
+//! - [`TextInput`][]: a multi-line text input dialog
+//! - [`TextViewer`][]: a scrollable text or file viewer
 //!
 //! These dialog boxes can be displayed using various backends:
 //! - [`Dialog`][]: uses `dialog` to display ncurses-based dialog boxes (requires the external
 //!   `dialog` tool)
+//! - [`Editor`][]: opens the text editor set in `VISUAL` or `EDITOR` for input dialogs and uses
+//!   [`Stdio`][] for all other dialogs
 //! - [`KDialog`][]: uses `kdialog` to display Qt-based dialog boxes (requires the external
 //!   `kdialog` tool)
 //! - [`Stdio`][]: prints messages to the standard output and reads user input form standard input
-//!   (intended as a fallback backend)
+//!   or the controlling terminal (intended as a fallback backend)
 //! - [`Zenity`][]: uses `zenity` to display GTK-based dialog boxes (requires the external `zenity`
 //!   tool)
 //!
@@ -70,10 +80,20 @@
 //! ```
 //!
 //! [`Dialog`]: backends/struct.Dialog.html
+//! [`Editor`]: backends/struct.Editor.html
+//! [`Form`]: struct.Form.html
 //! [`Input`]: struct.Input.html
+//! [`TypedInput`]: struct.TypedInput.html
+//! [`License`]: struct.License.html
+//! [`Login`]: struct.Login.html
+//! [`Menu`]: struct.Menu.html
 //! [`Message`]: struct.Message.html
 //! [`Password`]: struct.Password.html
+//! [`ProgramBox`]: struct.ProgramBox.html
 //! [`Question`]: struct.Question.html
+//! [`Tail`]: struct.Tail.html
+//! [`TextInput`]: struct.TextInput.html
+//! [`TextViewer`]: struct.TextViewer.html
 //! [`KDialog`]: backends/struct.KDialog.html
 //! [`Stdio`]: backends/struct.Stdio.html
 //! [`Zenity`]: backends/struct.Zenity.html
@@ -82,6 +102,7 @@
 //! [`show_with`]: trait.DialogBox.html#method.show_with
 
 mod error;
+mod secret;
 
 /// Backends that display dialog boxes.
 ///
@@ -92,9 +113,23 @@ mod error;
 /// [`Backend`]: trait.Backend.html
 pub mod backends;
 
+use std::borrow;
+use std::cell;
 use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::marker;
+use std::path;
+use std::process;
+use std::str;
+use std::sync;
+use std::thread;
 
 pub use crate::error::{Error, Result};
+pub use crate::secret::Secret;
+#[cfg(feature = "derive")]
+pub use dialog_derive::Dialog;
 
 /// A dialog box that can be shown using a backend.
 ///
@@ -116,168 +151,1420 @@ pub trait DialogBox {
         B: backends::Backend + ?Sized;
 }
 
+/// A type that can be queried from the user with a dialog box.
+///
+/// This trait is usually implemented using `#[derive(Dialog)]` from the `dialog-derive` crate,
+/// which is re-exported as [`Dialog`][] if the `derive` feature is enabled.  For structs, the
+/// derived implementation shows a [`Form`][] with one field per struct field and parses the values
+/// using `FromStr`.  For enums without fields, it shows a [`Menu`][] with one item per variant.
+///
+/// # Example
+///
+/// ```no_run
+/// use dialog::{DialogBox, Field, FromDialog};
+///
+/// struct Server {
+///     host: String,
+///     port: u16,
+/// }
+///
+/// impl FromDialog for Server {
+///     fn from_dialog_with<B>(backend: impl AsRef<B>) -> dialog::Result<Option<Self>>
+///     where
+///         B: dialog::backends::Backend + ?Sized,
+///     {
+///         dialog::Form::new("Please enter the server address")
+///             .field(Field::text("Host"))
+///             .field(Field::text("Port").with_value("22"))
+///             .convert_with(backend, |values| {
+///                 let port = values[1].parse().map_err(|_| "Invalid port")?;
+///                 Ok(Server { host: values[0].clone(), port })
+///             })
+///     }
+/// }
+///
+/// let server = Server::from_dialog().expect("Could not display dialog box");
+/// ```
+///
+/// [`Dialog`]: derive.Dialog.html
+/// [`Form`]: struct.Form.html
+/// [`Menu`]: struct.Menu.html
+pub trait FromDialog: Sized {
+    /// Queries a value using the default backend, or returns `None` if the dialog is cancelled.
+    ///
+    /// `T::from_dialog()` is a shorthand for `T::from_dialog_with(default_backend())`.
+    fn from_dialog() -> Result<Option<Self>> {
+        Self::from_dialog_with(default_backend())
+    }
+
+    /// Queries a value using the given backend, or returns `None` if the dialog is cancelled.
+    fn from_dialog_with<B>(backend: impl AsRef<B>) -> Result<Option<Self>>
+    where
+        B: backends::Backend + ?Sized;
+}
+
 /// A message box.
 ///
-/// This dialog box displays a text and an optional title and has a single OK button.  It does not
-/// produce any output.
+/// This dialog box displays a text and an optional title and has a single OK button.  It does not
+/// produce any output.
+///
+/// # Example
+///
+/// ```no_run
+/// use dialog::DialogBox;
+///
+/// dialog::Message::new("The operation was successful.")
+///     .title("Success")
+///     .show()
+///     .expect("Could not display dialog box");
+/// ```
+pub struct Message {
+    text: String,
+    title: Option<String>,
+}
+
+impl Message {
+    /// Creates a new message box with the given text.
+    pub fn new(text: impl Into<String>) -> Message {
+        Message {
+            text: text.into(),
+            title: None,
+        }
+    }
+
+    /// Sets the title of this message box.
+    ///
+    /// This method returns a reference to `self` to enable chaining.
+    pub fn title(&mut self, title: impl Into<String>) -> &mut Message {
+        self.title = Some(title.into());
+        self
+    }
+}
+
+impl DialogBox for Message {
+    type Output = ();
+
+    fn show_with<B>(&self, backend: impl AsRef<B>) -> Result<Self::Output>
+    where
+        B: backends::Backend + ?Sized,
+    {
+        backend.as_ref().show_message(self)
+    }
+}
+
+/// A function that checks a user input and returns an error message if the input is not valid.
+///
+/// Checks are `Send` and `Sync` so that dialog boxes with checks can be shared between threads.
+type Check = dyn Fn(&str) -> std::result::Result<(), String> + Send + Sync;
+
+/// A dialog box with multiple labelled input fields.
+///
+/// This dialog box displays a text and the [`Field`][]s added with [`field`][].  It returns the
+/// values of all fields in the order they have been added, including read-only fields, or `None`
+/// if the user cancelled the dialog.
+///
+/// [`KDialog`][] and [`Stdio`][] ask for the values of the fields one after another.
+/// [`Zenity`][] does not support default values and displays read-only fields as part of the
+/// text.
+///
+/// # Example
+///
+/// ```no_run
+/// use dialog::{DialogBox, Field};
+///
+/// let values = dialog::Form::new("Please enter the connection settings")
+///     .title("Connection")
+///     .field(Field::read_only("Server", "example.org"))
+///     .field(Field::text("Username").with_value("guest"))
+///     .field(Field::password("Password"))
+///     .show()
+///     .expect("Could not display dialog box");
+/// if let Some(values) = values {
+///     println!("Connecting to {} as {}", values[0], values[1]);
+/// }
+/// ```
+///
+/// [`Field`]: struct.Field.html
+/// [`field`]: #method.field
+/// [`KDialog`]: backends/struct.KDialog.html
+/// [`Stdio`]: backends/struct.Stdio.html
+/// [`Zenity`]: backends/struct.Zenity.html
+pub struct Form {
+    text: String,
+    title: Option<String>,
+    fields: Vec<Field>,
+}
+
+impl Form {
+    /// Creates a new form dialog box with the given text and without fields.
+    pub fn new(text: impl Into<String>) -> Form {
+        Form {
+            text: text.into(),
+            title: None,
+            fields: Vec::new(),
+        }
+    }
+
+    /// Sets the title of this form dialog box.
+    ///
+    /// This method returns a reference to `self` to enable chaining.
+    pub fn title(&mut self, title: impl Into<String>) -> &mut Form {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Adds the given field to this form dialog box.
+    ///
+    /// This method returns a reference to `self` to enable chaining.
+    pub fn field(&mut self, field: Field) -> &mut Form {
+        self.fields.push(field);
+        self
+    }
+
+    /// Shows this form dialog box using the given backend until the values can be converted with
+    /// the given function.
+    ///
+    /// The function receives the values of all fields.  If it returns an error, the error message
+    /// is displayed together with the text of this form, and the user can correct the values.
+    pub fn convert_with<B, T, F>(&self, backend: impl AsRef<B>, convert: F) -> Result<Option<T>>
+    where
+        B: backends::Backend + ?Sized,
+        F: Fn(&[String]) -> std::result::Result<T, String>,
+    {
+        let backend = backend.as_ref();
+        let mut retry: Option<Form> = None;
+        loop {
+            let values = match backend.show_form(retry.as_ref().unwrap_or(self))? {
+                Some(values) => values,
+                None => return Ok(None),
+            };
+            match convert(&values) {
+                Ok(value) => return Ok(Some(value)),
+                Err(err) => retry = Some(self.retry(&err, values)),
+            }
+        }
+    }
+
+    /// Returns a copy of this form dialog box with the given error and the given values.
+    ///
+    /// The values of password fields are not carried over.
+    fn retry(&self, error: &str, values: Vec<String>) -> Form {
+        Form {
+            text: format!("{}\n\n{}", error, self.text),
+            title: self.title.clone(),
+            fields: self
+                .fields
+                .iter()
+                .zip(values)
+                .map(|(field, value)| match field.kind {
+                    FieldKind::Password => field.clone(),
+                    _ => field.clone().with_value(value),
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the values for all fields given the values of all fields or of the editable fields.
+    ///
+    /// Returns an error if the number of values does not match the number of fields.
+    fn merge_values(&self, values: Vec<String>) -> Result<Vec<String>> {
+        if values.len() == self.fields.len() {
+            return Ok(values);
+        }
+        let editable = self
+            .fields
+            .iter()
+            .filter(|field| field.is_editable())
+            .count();
+        if values.len() != editable {
+            return Err(Error::from(
+                "The number of form values does not match the fields",
+            ));
+        }
+        let mut values = values.into_iter();
+        Ok(self
+            .fields
+            .iter()
+            .map(|field| match field.kind {
+                FieldKind::ReadOnly => field.value.clone(),
+                _ => values.next().unwrap_or_default(),
+            })
+            .collect())
+    }
+}
+
+impl DialogBox for Form {
+    type Output = Option<Vec<String>>;
+
+    fn show_with<B>(&self, backend: impl AsRef<B>) -> Result<Self::Output>
+    where
+        B: backends::Backend + ?Sized,
+    {
+        backend.as_ref().show_form(self)
+    }
+}
+
+/// A labelled field in a [`Form`][] dialog box.
+///
+/// [`Form`]: struct.Form.html
+#[derive(Clone, Debug, PartialEq)]
+pub struct Field {
+    label: String,
+    value: String,
+    kind: FieldKind,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum FieldKind {
+    Text,
+    Password,
+    ReadOnly,
+}
+
+impl Field {
+    /// Creates a new text field with the given label.
+    pub fn text(label: impl Into<String>) -> Field {
+        Field::new(label.into(), String::new(), FieldKind::Text)
+    }
+
+    /// Creates a new password field with the given label.
+    ///
+    /// The input is hidden, but the value is returned as a plain string.  Password fields are
+    /// always empty when the dialog is shown, so a value set with [`with_value`][] is ignored.
+    ///
+    /// [`with_value`]: #method.with_value
+    pub fn password(label: impl Into<String>) -> Field {
+        Field::new(label.into(), String::new(), FieldKind::Password)
+    }
+
+    /// Creates a new read-only field with the given label and value.
+    pub fn read_only(label: impl Into<String>, value: impl Into<String>) -> Field {
+        Field::new(label.into(), value.into(), FieldKind::ReadOnly)
+    }
+
+    fn new(label: String, value: String, kind: FieldKind) -> Field {
+        Field { label, value, kind }
+    }
+
+    /// Returns this field with the given value, for example the default value of a text field.
+    pub fn with_value(mut self, value: impl Into<String>) -> Field {
+        self.value = value.into();
+        self
+    }
+
+    fn is_editable(&self) -> bool {
+        self.kind != FieldKind::ReadOnly
+    }
+}
+
+/// A dialog box with a text input field.
+///
+/// This dialog box displays a text and an input field.  It returns the text entered by the user or
+/// `None` if the user cancelled the dialog.
+///
+/// The input can be checked using [`validate`][].  If the input is not valid, the dialog box is
+/// shown again with the error message prefixed to the text and the previous input pre-filled.
+///
+/// # Example
+///
+/// ```no_run
+/// use dialog::DialogBox;
+///
+/// let name = dialog::Input::new("Please enter your name")
+///     .title("Name")
+///     .show()
+///     .expect("Could not display dialog box");
+/// match name {
+///     Some(name) => println!("Hello {}!", name),
+///     None => println!("Hello stranger!"),
+/// };
+/// ```
+///
+/// Ask for an email address until the input is valid:
+///
+/// ```no_run
+/// use dialog::DialogBox;
+///
+/// let email = dialog::Input::new("Please enter your email address")
+///     .title("Email")
+///     .validate(|email| match email.contains('@') {
+///         true => Ok(()),
+///         false => Err("This is not a valid email address.".to_string()),
+///     })
+///     .show()
+///     .expect("Could not display dialog box");
+/// ```
+///
+/// [`validate`]: #method.validate
+pub struct Input {
+    text: String,
+    title: Option<String>,
+    default: Option<String>,
+    history: Option<String>,
+    checks: Vec<Box<Check>>,
+}
+
+impl Input {
+    /// Creates a new input dialog box with the given text.
+    pub fn new(text: impl Into<String>) -> Input {
+        Input {
+            text: text.into(),
+            title: None,
+            default: None,
+            history: None,
+            checks: Vec::new(),
+        }
+    }
+
+    /// Sets the title of this input box.
+    ///
+    /// This method returns a reference to `self` to enable chaining.
+    pub fn title(&mut self, title: impl Into<String>) -> &mut Input {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the default value of this input box.
+    ///
+    /// This method returns a reference to `self` to enable chaining.
+    pub fn default(&mut self, default: impl Into<String>) -> &mut Input {
+        self.default = Some(default.into());
+        self
+    }
+
+    /// Enables the persistent input history for this input box using the given ID.
+    ///
+    /// Input boxes with the same ID share their history.  Only some backends support an input
+    /// history, for example the [`Stdio`][] backend, which stores the history in the XDG data
+    /// directory.  The history might contain sensitive data, so it is only accessible by the
+    /// user.
+    ///
+    /// This method returns a reference to `self` to enable chaining.
+    ///
+    /// [`Stdio`]: backends/struct.Stdio.html
+    pub fn history(&mut self, id: impl Into<String>) -> &mut Input {
+        self.history = Some(id.into());
+        self
+    }
+
+    /// Validates the input using the given function.
+    ///
+    /// The function returns an error message if the input is not valid.  In this case, the input
+    /// box is shown again with the error message prefixed to the text and the invalid input
+    /// pre-filled until the user enters a valid input or cancels the dialog.
+    ///
+    /// This method returns a reference to `self` to enable chaining.
+    pub fn validate<F>(&mut self, f: F) -> &mut Input
+    where
+        F: Fn(&str) -> std::result::Result<(), String> + Send + Sync + 'static,
+    {
+        self.checks.push(Box::new(f));
+        self
+    }
+
+    /// Shows this input box using the default backend and parses the input.
+    ///
+    /// If the input cannot be parsed, the input box is shown again with the parse error prefixed
+    /// to the text, see [`TypedInput`][].  `input.parse()` is a shorthand for
+    /// `input.parse_with(default_backend())`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// let port: Option<u16> = dialog::Input::new("Port")
+    ///     .parse()
+    ///     .expect("Could not display dialog box");
+    /// ```
+    ///
+    /// [`TypedInput`]: struct.TypedInput.html
+    pub fn parse<T>(&self) -> Result<Option<T>>
+    where
+        T: str::FromStr,
+        T::Err: fmt::Display,
+    {
+        self.parse_with(default_backend())
+    }
+
+    /// Shows this input box using the given backend and parses the input.
+    ///
+    /// See [`parse`][] for more information.
+    ///
+    /// [`parse`]: #method.parse
+    pub fn parse_with<B, T>(&self, backend: impl AsRef<B>) -> Result<Option<T>>
+    where
+        B: backends::Backend + ?Sized,
+        T: str::FromStr,
+        T::Err: fmt::Display,
+    {
+        self.show_checked(backend.as_ref(), |input| {
+            input.parse().map_err(|err: T::Err| err.to_string())
+        })
+    }
+
+    /// Shows this input box until the input is valid and can be converted with the given function.
+    fn show_checked<B, T, F>(&self, backend: &B, convert: F) -> Result<Option<T>>
+    where
+        B: backends::Backend + ?Sized,
+        F: Fn(&str) -> std::result::Result<T, String>,
+    {
+        let mut retry: Option<Input> = None;
+        loop {
+            let input = match backend.show_input(retry.as_ref().unwrap_or(self))? {
+                Some(input) => input,
+                None => return Ok(None),
+            };
+            let result = self
+                .checks
+                .iter()
+                .try_for_each(|check| check(&input))
+                .and_then(|_| convert(&input));
+            match result {
+                Ok(value) => return Ok(Some(value)),
+                Err(err) => retry = Some(self.retry(&err, input)),
+            }
+        }
+    }
+
+    /// Returns a copy of this input box with the given error and the given pre-filled input.
+    fn retry(&self, error: &str, input: String) -> Input {
+        Input {
+            text: format!("{}\n\n{}", error, self.text),
+            title: self.title.clone(),
+            default: Some(input),
+            history: self.history.clone(),
+            checks: Vec::new(),
+        }
+    }
+}
+
+impl DialogBox for Input {
+    type Output = Option<String>;
+
+    fn show_with<B>(&self, backend: impl AsRef<B>) -> Result<Self::Output>
+    where
+        B: backends::Backend + ?Sized,
+    {
+        self.show_checked(backend.as_ref(), |input| Ok(input.to_owned()))
+    }
+}
+
+/// A dialog box with a text input field that parses the input.
+///
+/// This dialog box works like [`Input`][], but parses the input using the [`FromStr`][]
+/// implementation of `T`.  If the input cannot be parsed, the dialog box is shown again with the
+/// parse error prefixed to the text and the previous input pre-filled.  It returns the parsed
+/// value or `None` if the user cancelled the dialog.
+///
+/// # Example
+///
+/// ```no_run
+/// use dialog::DialogBox;
+///
+/// let port = dialog::TypedInput::<u16>::new("Please enter the port")
+///     .title("Port")
+///     .default(8080)
+///     .show()
+///     .expect("Could not display dialog box");
+/// match port {
+///     Some(port) => println!("Listening on port {}", port),
+///     None => println!("Not listening"),
+/// };
+/// ```
+///
+/// [`Input`]: struct.Input.html
+/// [`FromStr`]: https://doc.rust-lang.org/std/str/trait.FromStr.html
+pub struct TypedInput<T> {
+    input: Input,
+    marker: marker::PhantomData<fn() -> T>,
+}
+
+impl<T> TypedInput<T>
+where
+    T: str::FromStr,
+    T::Err: fmt::Display,
+{
+    /// Creates a new typed input dialog box with the given text.
+    pub fn new(text: impl Into<String>) -> TypedInput<T> {
+        TypedInput::from(Input::new(text))
+    }
+
+    /// Sets the title of this input box.
+    ///
+    /// This method returns a reference to `self` to enable chaining.
+    pub fn title(&mut self, title: impl Into<String>) -> &mut TypedInput<T> {
+        self.input.title(title);
+        self
+    }
+
+    /// Sets the default value of this input box.
+    ///
+    /// The value is formatted using its `Display` implementation.
+    ///
+    /// This method returns a reference to `self` to enable chaining.
+    pub fn default(&mut self, default: T) -> &mut TypedInput<T>
+    where
+        T: fmt::Display,
+    {
+        self.input.default(default.to_string());
+        self
+    }
+
+    /// Enables the persistent input history for this input box using the given ID.
+    ///
+    /// See [`Input::history`][] for more information.
+    ///
+    /// This method returns a reference to `self` to enable chaining.
+    ///
+    /// [`Input::history`]: struct.Input.html#method.history
+    pub fn history(&mut self, id: impl Into<String>) -> &mut TypedInput<T> {
+        self.input.history(id);
+        self
+    }
+
+    /// Validates the input using the given function before it is parsed.
+    ///
+    /// See [`Input::validate`][] for more information.
+    ///
+    /// This method returns a reference to `self` to enable chaining.
+    ///
+    /// [`Input::validate`]: struct.Input.html#method.validate
+    pub fn validate<F>(&mut self, f: F) -> &mut TypedInput<T>
+    where
+        F: Fn(&str) -> std::result::Result<(), String> + Send + Sync + 'static,
+    {
+        self.input.validate(f);
+        self
+    }
+}
+
+impl<T> From<Input> for TypedInput<T> {
+    fn from(input: Input) -> TypedInput<T> {
+        TypedInput {
+            input,
+            marker: marker::PhantomData,
+        }
+    }
+}
+
+impl<T> DialogBox for TypedInput<T>
+where
+    T: str::FromStr,
+    T::Err: fmt::Display,
+{
+    type Output = Option<T>;
+
+    fn show_with<B>(&self, backend: impl AsRef<B>) -> Result<Self::Output>
+    where
+        B: backends::Backend + ?Sized,
+    {
+        self.input.parse_with(backend)
+    }
+}
+
+/// A dialog box with a multi-line text input field.
+///
+/// This dialog box displays a text and a multi-line input field that can be pre-filled using
+/// [`default`][].  It returns the text entered by the user without trailing line breaks or `None`
+/// if the user cancelled the dialog.
+///
+/// The [`Stdio`][] backend reads the text until a line that only contains a period or until the
+/// end of the input.  [`Dialog`][] and [`Zenity`][] do not display the text.
+///
+/// # Example
+///
+/// ```no_run
+/// use dialog::DialogBox;
+///
+/// let notes = dialog::TextInput::new("Please enter your notes")
+///     .title("Notes")
+///     .default("TODO:\n")
+///     .show()
+///     .expect("Could not display dialog box");
+/// if let Some(notes) = notes {
+///     println!("Your notes:\n{}", notes);
+/// }
+/// ```
+///
+/// [`default`]: #method.default
+/// [`Dialog`]: backends/struct.Dialog.html
+/// [`Stdio`]: backends/struct.Stdio.html
+/// [`Zenity`]: backends/struct.Zenity.html
+pub struct TextInput {
+    text: String,
+    title: Option<String>,
+    default: Option<String>,
+}
+
+impl TextInput {
+    /// Creates a new text input dialog box with the given text.
+    pub fn new(text: impl Into<String>) -> TextInput {
+        TextInput {
+            text: text.into(),
+            title: None,
+            default: None,
+        }
+    }
+
+    /// Sets the title of this text input box.
+    ///
+    /// This method returns a reference to `self` to enable chaining.
+    pub fn title(&mut self, title: impl Into<String>) -> &mut TextInput {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the initial text of this text input box.
+    ///
+    /// This method returns a reference to `self` to enable chaining.
+    pub fn default(&mut self, default: impl Into<String>) -> &mut TextInput {
+        self.default = Some(default.into());
+        self
+    }
+}
+
+impl DialogBox for TextInput {
+    type Output = Option<String>;
+
+    fn show_with<B>(&self, backend: impl AsRef<B>) -> Result<Self::Output>
+    where
+        B: backends::Backend + ?Sized,
+    {
+        backend.as_ref().show_text_input(self)
+    }
+}
+
+/// A dialog box that displays a scrollable text.
+///
+/// This dialog box displays a text or the content of a file, for example a license text or a log
+/// file.  It does not produce any output.
+///
+/// The [`Stdio`][] backend uses the pager set in the `PAGER` environment variable if the output
+/// is a terminal, or a built-in pager otherwise.
+///
+/// # Example
+///
+/// ```no_run
+/// use dialog::DialogBox;
+///
+/// dialog::TextViewer::from_file("/var/log/installer.log")
+///     .title("Installation log")
+///     .show()
+///     .expect("Could not display dialog box");
+/// ```
+///
+/// [`Stdio`]: backends/struct.Stdio.html
+pub struct TextViewer {
+    source: TextSource,
+    title: Option<String>,
+}
+
+impl TextViewer {
+    /// Creates a new text viewer that displays the given text.
+    pub fn new(text: impl Into<String>) -> TextViewer {
+        TextViewer {
+            source: TextSource::Text(text.into()),
+            title: None,
+        }
+    }
+
+    /// Creates a new text viewer that displays the content of the file with the given path.
+    pub fn from_file(path: impl Into<path::PathBuf>) -> TextViewer {
+        TextViewer {
+            source: TextSource::File(path.into()),
+            title: None,
+        }
+    }
+
+    /// Sets the title of this text viewer.
+    ///
+    /// This method returns a reference to `self` to enable chaining.
+    pub fn title(&mut self, title: impl Into<String>) -> &mut TextViewer {
+        self.title = Some(title.into());
+        self
+    }
+}
+
+impl DialogBox for TextViewer {
+    type Output = ();
+
+    fn show_with<B>(&self, backend: impl AsRef<B>) -> Result<Self::Output>
+    where
+        B: backends::Backend + ?Sized,
+    {
+        backend.as_ref().show_text_viewer(self)
+    }
+}
+
+/// A dialog box that asks the user to accept a license agreement.
+///
+/// This dialog box displays a scrollable text or the content of a file, like [`TextViewer`][],
+/// and asks the user to confirm the acceptance with a checkbox or a separate question.  It returns
+/// `true` if the user accepted the license and `false` otherwise.
+///
+/// The [`Stdio`][] backend requires the user to type the full answer, for example `yes`.
+///
+/// # Example
+///
+/// ```no_run
+/// use dialog::DialogBox;
+///
+/// let accepted = dialog::License::from_file("LICENSE")
+///     .title("License agreement")
+///     .show()
+///     .expect("Could not display dialog box");
+/// if !accepted {
+///     std::process::exit(1);
+/// }
+/// ```
+///
+/// [`TextViewer`]: struct.TextViewer.html
+/// [`Stdio`]: backends/struct.Stdio.html
+pub struct License {
+    source: TextSource,
+    title: Option<String>,
+    label: String,
+}
+
+impl License {
+    /// Creates a new license dialog box that displays the given text.
+    pub fn new(text: impl Into<String>) -> License {
+        License::with_source(TextSource::Text(text.into()))
+    }
+
+    /// Creates a new license dialog box that displays the content of the file with the given path.
+    pub fn from_file(path: impl Into<path::PathBuf>) -> License {
+        License::with_source(TextSource::File(path.into()))
+    }
+
+    fn with_source(source: TextSource) -> License {
+        License {
+            source,
+            title: None,
+            label: "I accept the terms of the license agreement".to_string(),
+        }
+    }
+
+    /// Sets the title of this license dialog box.
+    ///
+    /// This method returns a reference to `self` to enable chaining.
+    pub fn title(&mut self, title: impl Into<String>) -> &mut License {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the label of the checkbox or the question that confirms the acceptance.
+    ///
+    /// Per default, the label is `I accept the terms of the license agreement`.
+    ///
+    /// This method returns a reference to `self` to enable chaining.
+    pub fn label(&mut self, label: impl Into<String>) -> &mut License {
+        self.label = label.into();
+        self
+    }
+}
+
+impl DialogBox for License {
+    type Output = bool;
+
+    fn show_with<B>(&self, backend: impl AsRef<B>) -> Result<Self::Output>
+    where
+        B: backends::Backend + ?Sized,
+    {
+        backend.as_ref().show_license(self)
+    }
+}
+
+/// A dialog box that follows a growing file, for example the log file of a running job.
+///
+/// This dialog box displays the content of a file and the data that is appended to it, similar to
+/// `tail -f`.  It is closed when the user closes it or when the [`Completion`][] handle set with
+/// [`completion`][] signals that the file is complete.  It does not produce any output.
+///
+/// If a completion handle is set and the file does not exist yet, the dialog box waits until the
+/// file is created before it is displayed.  Without a completion handle, the file must exist when
+/// the dialog box is shown.
+///
+/// [`KDialog`][] does not support following a file, so it only displays the content of the file
+/// when the dialog is opened.  The [`Stdio`][] backend stops following the file when the user
+/// presses Enter.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::thread;
+///
+/// use dialog::DialogBox;
+///
+/// let completion = dialog::Completion::new();
+/// let handle = completion.clone();
+/// let job = thread::spawn(move || {
+///     // run the job and write the log to /tmp/job.log
+///     handle.complete();
+/// });
+/// dialog::Tail::new("/tmp/job.log")
+///     .title("Job log")
+///     .completion(completion)
+///     .show()
+///     .expect("Could not display dialog box");
+/// job.join().expect("The job failed");
+/// ```
+///
+/// [`Completion`]: struct.Completion.html
+/// [`completion`]: #method.completion
+/// [`KDialog`]: backends/struct.KDialog.html
+/// [`Stdio`]: backends/struct.Stdio.html
+pub struct Tail {
+    path: path::PathBuf,
+    title: Option<String>,
+    completion: Option<Completion>,
+}
+
+impl Tail {
+    /// Creates a new tail dialog box that follows the file with the given path.
+    pub fn new(path: impl Into<path::PathBuf>) -> Tail {
+        Tail {
+            path: path.into(),
+            title: None,
+            completion: None,
+        }
+    }
+
+    /// Sets the title of this tail dialog box.
+    ///
+    /// This method returns a reference to `self` to enable chaining.
+    pub fn title(&mut self, title: impl Into<String>) -> &mut Tail {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the handle that signals that the file is complete and that the dialog box should be
+    /// closed.
+    ///
+    /// This method returns a reference to `self` to enable chaining.
+    pub fn completion(&mut self, completion: Completion) -> &mut Tail {
+        self.completion = Some(completion);
+        self
+    }
+
+    fn is_complete(&self) -> bool {
+        self.completion
+            .as_ref()
+            .is_some_and(|completion| completion.is_complete())
+    }
+
+    /// Waits until the file exists or the completion is signaled.
+    ///
+    /// Returns immediately if no completion handle is set.
+    fn wait_for_file(&self) {
+        if self.completion.is_none() {
+            return;
+        }
+        while !self.path.exists() && !self.is_complete() {
+            thread::sleep(backends::POLL_INTERVAL);
+        }
+    }
+}
+
+impl DialogBox for Tail {
+    type Output = ();
+
+    fn show_with<B>(&self, backend: impl AsRef<B>) -> Result<Self::Output>
+    where
+        B: backends::Backend + ?Sized,
+    {
+        backend.as_ref().show_tail(self)
+    }
+}
+
+/// A handle that signals the completion of a job, for example to close a [`Tail`][] dialog box.
+///
+/// All clones of a handle share their state, so one clone can be passed to the job and another one
+/// to the dialog box.
+///
+/// [`Tail`]: struct.Tail.html
+#[derive(Clone, Debug, Default)]
+pub struct Completion(sync::Arc<sync::atomic::AtomicBool>);
+
+impl Completion {
+    /// Creates a new handle that has not been completed.
+    pub fn new() -> Completion {
+        Completion::default()
+    }
+
+    /// Signals the completion.
+    pub fn complete(&self) {
+        self.0.store(true, sync::atomic::Ordering::SeqCst);
+    }
+
+    /// Returns whether the completion has been signaled.
+    pub fn is_complete(&self) -> bool {
+        self.0.load(sync::atomic::Ordering::SeqCst)
+    }
+}
+
+/// The text displayed in a text viewer or a license dialog box.
+enum TextSource {
+    Text(String),
+    File(path::PathBuf),
+}
+
+impl TextSource {
+    /// Returns the text, reading it from the file if necessary.
+    fn read(&self) -> Result<borrow::Cow<'_, str>> {
+        match self {
+            TextSource::Text(text) => Ok(borrow::Cow::Borrowed(text)),
+            TextSource::File(path) => Ok(borrow::Cow::Owned(fs::read_to_string(path)?)),
+        }
+    }
+}
+
+/// A dialog box that runs a command and displays its output.
+///
+/// This dialog box spawns the given command and displays its standard output and standard error
+/// while it is running.  It returns the exit status of the command.  The standard input of the
+/// command is connected to `/dev/null`, and its standard streams are reset when it has been
+/// spawned.
+///
+/// [`KDialog`][] cannot display the output while the command is running, so it displays the
+/// output once the command has finished.  [`Zenity`][] does not display the text.  If the user
+/// closes the dialog box while the command is running, the command might be terminated when it
+/// writes to its closed output.
 ///
 /// # Example
 ///
 /// ```no_run
+/// use std::process;
+///
 /// use dialog::DialogBox;
 ///
-/// dialog::Message::new("The operation was successful.")
-///     .title("Success")
+/// let mut command = process::Command::new("cargo");
+/// command.arg("build");
+/// let status = dialog::ProgramBox::new(command)
+///     .title("Building")
 ///     .show()
 ///     .expect("Could not display dialog box");
+/// println!("cargo build finished with {}", status);
 /// ```
-pub struct Message {
-    text: String,
+///
+/// [`KDialog`]: backends/struct.KDialog.html
+/// [`Zenity`]: backends/struct.Zenity.html
+pub struct ProgramBox {
+    command: cell::RefCell<process::Command>,
+    text: Option<String>,
     title: Option<String>,
 }
 
-impl Message {
-    /// Creates a new message box with the given text.
-    pub fn new(text: impl Into<String>) -> Message {
-        Message {
-            text: text.into(),
+impl ProgramBox {
+    /// Creates a new program box that runs the given command.
+    pub fn new(command: process::Command) -> ProgramBox {
+        ProgramBox {
+            command: cell::RefCell::new(command),
+            text: None,
             title: None,
         }
     }
 
-    /// Sets the title of this message box.
+    /// Sets the text that is displayed above the output of the command.
     ///
     /// This method returns a reference to `self` to enable chaining.
-    pub fn title(&mut self, title: impl Into<String>) -> &mut Message {
+    pub fn text(&mut self, text: impl Into<String>) -> &mut ProgramBox {
+        self.text = Some(text.into());
+        self
+    }
+
+    /// Sets the title of this program box.
+    ///
+    /// This method returns a reference to `self` to enable chaining.
+    pub fn title(&mut self, title: impl Into<String>) -> &mut ProgramBox {
         self.title = Some(title.into());
         self
     }
+
+    /// Spawns the command and returns a reader for its merged standard output and standard error.
+    fn spawn(&self) -> Result<(process::Child, io::PipeReader)> {
+        let mut command = self.command.borrow_mut();
+        let (reader, writer) = io::pipe()?;
+        command.stdin(process::Stdio::null());
+        command.stdout(writer.try_clone()?);
+        command.stderr(writer);
+        let child = command.spawn();
+        // The command keeps the write end of the pipe open, so the reader would never see EOF
+        command.stdout(process::Stdio::null());
+        command.stderr(process::Stdio::null());
+        Ok((child?, reader))
+    }
 }
 
-impl DialogBox for Message {
-    type Output = ();
+impl DialogBox for ProgramBox {
+    type Output = process::ExitStatus;
 
     fn show_with<B>(&self, backend: impl AsRef<B>) -> Result<Self::Output>
     where
         B: backends::Backend + ?Sized,
     {
-        backend.as_ref().show_message(self)
+        backend.as_ref().show_program_box(self)
     }
 }
 
-/// A dialog box with a text input field.
+/// A dialog box with a password input field.
 ///
-/// This dialog box displays a text and an input field.  It returns the text entered by the user or
-/// `None` if the user cancelled the dialog.
+/// This dialog box displays a text and a password input field.  It returns the password entered by
+/// the user as a [`Secret`][] or `None` if the user cancelled the dialog.
+///
+/// If a confirmation text is set using [`confirm`][], the user has to enter the password twice.
+/// The password can also be checked against a policy using [`min_length`][], [`require`][] and
+/// [`check`][].  If the passwords do not match or if the password violates the policy, the error
+/// is displayed in a message box using the same backend and the user is asked again.  The error
+/// messages are localized using a [`Catalog`][], see [`catalog`][].
 ///
 /// # Example
 ///
 /// ```no_run
 /// use dialog::DialogBox;
 ///
-/// let name = dialog::Input::new("Please enter your name")
-///     .title("Name")
+/// let password = dialog::Password::new("Please enter a new password")
+///     .title("Password")
 ///     .show()
 ///     .expect("Could not display dialog box");
-/// match name {
-///     Some(name) => println!("Hello {}!", name),
-///     None => println!("Hello stranger!"),
+/// match password {
+///     Some(password) => println!("Your new password is: {}", password.expose()),
+///     None => println!("You do not want to have a password."),
 /// };
 /// ```
-pub struct Input {
+///
+/// Ask for a new password that is confirmed by the user and that follows a policy:
+///
+/// ```no_run
+/// use dialog::{CharClass, DialogBox};
+///
+/// let password = dialog::Password::new("Please enter a new password")
+///     .title("Password")
+///     .confirm("Please repeat the password")
+///     .min_length(12)
+///     .require(CharClass::Digit)
+///     .check(|password| match password.contains("password") {
+///         true => Err("The password must not contain the word password.".to_string()),
+///         false => Ok(()),
+///     })
+///     .show()
+///     .expect("Could not display dialog box");
+/// ```
+///
+/// [`Secret`]: struct.Secret.html
+/// [`confirm`]: #method.confirm
+/// [`min_length`]: #method.min_length
+/// [`require`]: #method.require
+/// [`check`]: #method.check
+/// [`Catalog`]: backends/struct.Catalog.html
+/// [`catalog`]: #method.catalog
+pub struct Password {
     text: String,
     title: Option<String>,
-    default: Option<String>,
+    confirm: Option<String>,
+    min_length: usize,
+    required: Vec<CharClass>,
+    checks: Vec<Box<Check>>,
+    catalog: Option<backends::Catalog>,
 }
 
-impl Input {
-    /// Creates a new input dialog box with the given text.
-    pub fn new(text: impl Into<String>) -> Input {
-        Input {
+impl Password {
+    /// Creates a new password dialog box with the given text.
+    pub fn new(text: impl Into<String>) -> Password {
+        Password {
             text: text.into(),
             title: None,
-            default: None,
+            confirm: None,
+            min_length: 0,
+            required: Vec::new(),
+            checks: Vec::new(),
+            catalog: None,
         }
     }
 
-    /// Sets the title of this input box.
+    /// Sets the title of this password dialog box.
     ///
     /// This method returns a reference to `self` to enable chaining.
-    pub fn title(&mut self, title: impl Into<String>) -> &mut Input {
+    pub fn title(&mut self, title: impl Into<String>) -> &mut Password {
         self.title = Some(title.into());
         self
     }
 
-    /// Sets the default value of this input box.
+    /// Asks the user to confirm the password by entering it again with the given text.
+    ///
+    /// If the passwords do not match, the user is asked again.
     ///
     /// This method returns a reference to `self` to enable chaining.
-    pub fn default(&mut self, default: impl Into<String>) -> &mut Input {
-        self.default = Some(default.into());
+    pub fn confirm(&mut self, text: impl Into<String>) -> &mut Password {
+        self.confirm = Some(text.into());
+        self
+    }
+
+    /// Requires the password to have at least the given number of characters.
+    ///
+    /// This method returns a reference to `self` to enable chaining.
+    pub fn min_length(&mut self, length: usize) -> &mut Password {
+        self.min_length = length;
+        self
+    }
+
+    /// Requires the password to contain at least one character of the given class.
+    ///
+    /// This method returns a reference to `self` to enable chaining.
+    pub fn require(&mut self, class: CharClass) -> &mut Password {
+        if !self.required.contains(&class) {
+            self.required.push(class);
+        }
+        self
+    }
+
+    /// Checks the password using the given function.
+    ///
+    /// The function returns an error message if the password is not acceptable.  The message is
+    /// displayed to the user, who is then asked again.  The functions are called in the order they
+    /// were added, after the checks set with [`min_length`][] and [`require`][].
+    ///
+    /// This method returns a reference to `self` to enable chaining.
+    ///
+    /// [`min_length`]: #method.min_length
+    /// [`require`]: #method.require
+    pub fn check<F>(&mut self, f: F) -> &mut Password
+    where
+        F: Fn(&str) -> std::result::Result<(), String> + Send + Sync + 'static,
+    {
+        self.checks.push(Box::new(f));
+        self
+    }
+
+    /// Sets the catalog that is used for the messages if the passwords do not match or if the
+    /// password is too short or misses a required character class.
+    ///
+    /// Per default, the catalog for the locale set in the environment is used, see
+    /// [`Catalog::from_env`][].
+    ///
+    /// This method returns a reference to `self` to enable chaining.
+    ///
+    /// [`Catalog::from_env`]: backends/struct.Catalog.html#method.from_env
+    pub fn catalog(&mut self, catalog: backends::Catalog) -> &mut Password {
+        self.catalog = Some(catalog);
         self
     }
+
+    fn validate(
+        &self,
+        password: &Secret,
+        catalog: &backends::Catalog,
+    ) -> std::result::Result<(), String> {
+        let password = password.expose();
+        if password.chars().count() < self.min_length {
+            return Err(catalog.password_too_short(self.min_length));
+        }
+        for &class in &self.required {
+            if !password.chars().any(|c| class.contains(c)) {
+                return Err(catalog.password_missing(class).to_owned());
+            }
+        }
+        self.checks.iter().try_for_each(|check| check(password))
+    }
+
+    fn show_error<B>(&self, backend: &B, text: String) -> Result<()>
+    where
+        B: backends::Backend + ?Sized,
+    {
+        let mut message = Message::new(text);
+        if let Some(ref title) = self.title {
+            message.title(title.clone());
+        }
+        backend.show_message(&message)
+    }
 }
 
-impl DialogBox for Input {
-    type Output = Option<String>;
+impl DialogBox for Password {
+    type Output = Option<Secret>;
 
     fn show_with<B>(&self, backend: impl AsRef<B>) -> Result<Self::Output>
     where
         B: backends::Backend + ?Sized,
     {
-        backend.as_ref().show_input(self)
+        let backend = backend.as_ref();
+        let catalog = self
+            .catalog
+            .clone()
+            .unwrap_or_else(backends::Catalog::from_env);
+        loop {
+            let password = match backend.show_password(self)? {
+                Some(password) => password,
+                None => return Ok(None),
+            };
+            if let Err(err) = self.validate(&password, &catalog) {
+                self.show_error(backend, err)?;
+                continue;
+            }
+            if let Some(ref confirm) = self.confirm {
+                let mut dialog = Password::new(confirm.clone());
+                dialog.title = self.title.clone();
+                match backend.show_password(&dialog)? {
+                    Some(ref repeated) if *repeated == password => {}
+                    Some(_) => {
+                        self.show_error(backend, catalog.password_mismatch().to_owned())?;
+                        continue;
+                    }
+                    None => return Ok(None),
+                }
+            }
+            return Ok(Some(password));
+        }
     }
 }
 
-/// A dialog box with a password input field.
+/// A character class that can be required for a password, see [`Password::require`][].
 ///
-/// This dialog box displays a text and a password input field.  It returns the password entered by
-/// the user or `None` if the user cancelled the dialog.
+/// [`Password::require`]: struct.Password.html#method.require
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CharClass {
+    /// A lowercase letter.
+    Lowercase,
+    /// An uppercase letter.
+    Uppercase,
+    /// A digit.
+    Digit,
+    /// A character that is neither alphanumeric nor whitespace.
+    Symbol,
+}
+
+impl CharClass {
+    fn contains(self, c: char) -> bool {
+        match self {
+            CharClass::Lowercase => c.is_lowercase(),
+            CharClass::Uppercase => c.is_uppercase(),
+            CharClass::Digit => c.is_numeric(),
+            CharClass::Symbol => !c.is_alphanumeric() && !c.is_whitespace(),
+        }
+    }
+}
+
+/// A dialog box with a username and a password input field.
+///
+/// This dialog box displays a text and input fields for a username and a password.  It returns
+/// the [`Credentials`][] entered by the user or `None` if the user cancelled the dialog.  The
+/// username field can be pre-filled using [`username`][].
+///
+/// Some backends, for example [`KDialog`][] and [`Stdio`][], ask for the username and the
+/// password one after another.  [`Zenity`][] does not display the text and does not support
+/// pre-filling the username.
 ///
 /// # Example
 ///
 /// ```no_run
 /// use dialog::DialogBox;
 ///
-/// let password = dialog::Password::new("Please enter a new password")
-///     .title("Password")
+/// let credentials = dialog::Login::new("Please log in to the server")
+///     .title("Login")
+///     .username("guest")
 ///     .show()
 ///     .expect("Could not display dialog box");
-/// match password {
-///     Some(password) => println!("Your new password is: {}", password),
-///     None => println!("You do not want to have a password."),
+/// match credentials {
+///     Some(credentials) => println!("Hello {}!", credentials.username()),
+///     None => println!("Hello stranger!"),
 /// };
 /// ```
-pub struct Password {
+///
+/// [`Credentials`]: struct.Credentials.html
+/// [`username`]: #method.username
+/// [`KDialog`]: backends/struct.KDialog.html
+/// [`Stdio`]: backends/struct.Stdio.html
+/// [`Zenity`]: backends/struct.Zenity.html
+pub struct Login {
     text: String,
     title: Option<String>,
+    username: Option<String>,
+    username_label: String,
+    password_label: String,
 }
 
-impl Password {
-    /// Creates a new password dialog box with the given text.
-    pub fn new(text: impl Into<String>) -> Password {
-        Password {
+impl Login {
+    /// Creates a new login dialog box with the given text.
+    pub fn new(text: impl Into<String>) -> Login {
+        Login {
             text: text.into(),
             title: None,
+            username: None,
+            username_label: "Username".to_string(),
+            password_label: "Password".to_string(),
         }
     }
 
-    /// Sets the title of this password dialog box.
+    /// Sets the title of this login dialog box.
     ///
     /// This method returns a reference to `self` to enable chaining.
-    pub fn title(&mut self, title: impl Into<String>) -> &mut Password {
+    pub fn title(&mut self, title: impl Into<String>) -> &mut Login {
         self.title = Some(title.into());
         self
     }
+
+    /// Pre-fills the username field of this login dialog box.
+    ///
+    /// This method returns a reference to `self` to enable chaining.
+    pub fn username(&mut self, username: impl Into<String>) -> &mut Login {
+        self.username = Some(username.into());
+        self
+    }
+
+    /// Sets the labels of the username and the password field.
+    ///
+    /// Per default, the labels are `Username` and `Password`.  [`Zenity`][] always uses its own
+    /// labels.
+    ///
+    /// This method returns a reference to `self` to enable chaining.
+    ///
+    /// [`Zenity`]: backends/struct.Zenity.html
+    pub fn labels(
+        &mut self,
+        username: impl Into<String>,
+        password: impl Into<String>,
+    ) -> &mut Login {
+        self.username_label = username.into();
+        self.password_label = password.into();
+        self
+    }
 }
 
-impl DialogBox for Password {
-    type Output = Option<String>;
+impl DialogBox for Login {
+    type Output = Option<Credentials>;
 
     fn show_with<B>(&self, backend: impl AsRef<B>) -> Result<Self::Output>
     where
         B: backends::Backend + ?Sized,
     {
-        backend.as_ref().show_password(self)
+        backend.as_ref().show_login(self)
+    }
+}
+
+/// A username and a password entered in a [`Login`][] dialog box.
+///
+/// [`Login`]: struct.Login.html
+#[derive(Clone, Debug, PartialEq)]
+pub struct Credentials {
+    username: String,
+    password: Secret,
+}
+
+impl Credentials {
+    /// Creates new credentials from the given username and password.
+    pub fn new(username: impl Into<String>, password: Secret) -> Credentials {
+        Credentials {
+            username: username.into(),
+            password,
+        }
+    }
+
+    /// Returns the username.
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+
+    /// Returns the password.
+    pub fn password(&self) -> &Secret {
+        &self.password
     }
 }
 
@@ -311,6 +1598,7 @@ pub enum Choice {
 pub struct Question {
     text: String,
     title: Option<String>,
+    default: Option<Choice>,
 }
 
 impl Question {
@@ -319,6 +1607,7 @@ impl Question {
         Question {
             text: text.into(),
             title: None,
+            default: None,
         }
     }
 
@@ -329,6 +1618,24 @@ impl Question {
         self.title = Some(title.into());
         self
     }
+
+    /// Sets the default choice of this question dialog box.
+    ///
+    /// The default choice is selected if the user just confirms the dialog, for example by
+    /// pressing Enter.  Only [`Choice::Yes`][] and [`Choice::No`][] can be used as a default
+    /// choice.  Not all backends support setting the default choice.
+    ///
+    /// This method returns a reference to `self` to enable chaining.
+    ///
+    /// [`Choice::Yes`]: enum.Choice.html#variant.Yes
+    /// [`Choice::No`]: enum.Choice.html#variant.No
+    pub fn default(&mut self, default: Choice) -> &mut Question {
+        self.default = match default {
+            Choice::Cancel => None,
+            choice => Some(choice),
+        };
+        self
+    }
 }
 
 impl DialogBox for Question {
@@ -342,15 +1649,104 @@ impl DialogBox for Question {
     }
 }
 
+/// A menu dialog box.
+///
+/// This dialog box displays a text and a list of items that have been added with [`item`][].  The
+/// output is the index of the item selected by the user, or `None` if the dialog has been
+/// cancelled.
+///
+/// # Example
+///
+/// ```no_run
+/// use dialog::DialogBox;
+///
+/// let index = dialog::Menu::new("Please choose a color")
+///     .title("Color")
+///     .item("Red")
+///     .item("Green")
+///     .item("Blue")
+///     .show()
+///     .expect("Could not display dialog box");
+/// println!("The user chose item: {:?}", index);
+/// ```
+///
+/// [`item`]: #method.item
+pub struct Menu {
+    text: String,
+    title: Option<String>,
+    items: Vec<String>,
+}
+
+impl Menu {
+    /// Creates a new menu dialog box with the given text and without items.
+    pub fn new(text: impl Into<String>) -> Menu {
+        Menu {
+            text: text.into(),
+            title: None,
+            items: Vec::new(),
+        }
+    }
+
+    /// Sets the title of this menu dialog box.
+    ///
+    /// This method returns a reference to `self` to enable chaining.
+    pub fn title(&mut self, title: impl Into<String>) -> &mut Menu {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Adds an item with the given label to this menu dialog box.
+    ///
+    /// This method returns a reference to `self` to enable chaining.
+    pub fn item(&mut self, label: impl Into<String>) -> &mut Menu {
+        self.items.push(label.into());
+        self
+    }
+
+    /// Returns the tags and the labels of the items, where the tag is the one-based index.
+    fn tagged_items(&self) -> Vec<(String, &str)> {
+        self.items
+            .iter()
+            .enumerate()
+            .map(|(i, label)| ((i + 1).to_string(), label.as_str()))
+            .collect()
+    }
+
+    /// Returns the index of the item with the given tag.
+    fn parse_tag(&self, tag: &str) -> Result<usize> {
+        tag.trim()
+            .parse::<usize>()
+            .ok()
+            .filter(|tag| (1..=self.items.len()).contains(tag))
+            .map(|tag| tag - 1)
+            .ok_or_else(|| Error::from("The selected menu item is invalid"))
+    }
+}
+
+impl DialogBox for Menu {
+    type Output = Option<usize>;
+
+    fn show_with<B>(&self, backend: impl AsRef<B>) -> Result<Self::Output>
+    where
+        B: backends::Backend + ?Sized,
+    {
+        backend.as_ref().show_menu(self)
+    }
+}
+
 /// Creates a new instance of the default backend.
 ///
 /// The following steps are performed to determine the default backend:
-/// - If the `DIALOG` environment variable is set to a valid backend name, this backend is used.
-///   A valid backend name is the name of a struct in the `backends` module implementing the
-///   `Backend` trait in any case.
+/// - If the `DIALOG` environment variable is set, it is interpreted as a colon-separated list of
+///   backend names in order of preference, for example `DIALOG=kdialog:dialog:stdio`.  Whitespace
+///   around the names is ignored.  The first listed backend that is available is used.  A valid
+///   backend name is the name of a struct in the `backends` module implementing the `Backend`
+///   trait in any case, or `qarma` or `matedialog` to use the respective [`Zenity`][] clone.
+///   Unknown names are ignored; use [`default_backend_strict`][] to treat them as an error
+///   instead.
 /// - If the `DISPLAY` environment variable is set, the following resolution algorithm is used:
 ///   - If `XDG_CURRENT_DESKTOP=KDE`, [`KDialog`][]
-///   - [`Zenity`][]
+///   - [`Zenity`][], or one of its clones `qarma` and `matedialog`
 ///   - [`KDialog`][]
 /// - If the [`Dialog`][] backend is available, it is used.
 /// - Otherwise, a [`Stdio`][] instance is returned.
@@ -359,13 +1755,71 @@ impl DialogBox for Question {
 /// [`KDialog`]: backends/struct.KDialog.html
 /// [`Stdio`]: backends/struct.Stdio.html
 /// [`Zenity`]: backends/struct.Zenity.html
+/// [`default_backend_strict`]: fn.default_backend_strict.html
 pub fn default_backend() -> Box<dyn backends::Backend> {
-    if let Ok(backend) = env::var("DIALOG") {
-        if let Some(backend) = backends::from_str(&backend) {
-            return backend;
+    if let Ok(Some(backend)) = backend_from_env(false) {
+        return backend;
+    }
+    detect_backend()
+}
+
+/// Creates a new instance of the default backend, rejecting unknown backend names.
+///
+/// This function works like [`default_backend`][], but if the `DIALOG` environment variable
+/// contains a name that does not refer to a backend, an error is returned instead of silently
+/// ignoring that entry.  Entries that name a known backend whose tool is not installed are still
+/// skipped.
+///
+/// # Example
+///
+/// ```no_run
+/// use dialog::DialogBox;
+///
+/// let backend = dialog::default_backend_strict().expect("Invalid DIALOG variable");
+/// dialog::Message::new("Hello world!")
+///     .show_with(&backend)
+///     .expect("Could not display dialog box");
+/// ```
+///
+/// [`default_backend`]: fn.default_backend.html
+pub fn default_backend_strict() -> Result<Box<dyn backends::Backend>> {
+    match backend_from_env(true)? {
+        Some(backend) => Ok(backend),
+        None => Ok(detect_backend()),
+    }
+}
+
+fn backend_from_env(strict: bool) -> Result<Option<Box<dyn backends::Backend>>> {
+    let names = match env::var("DIALOG") {
+        Ok(names) => names,
+        Err(_) => return Ok(None),
+    };
+    let names: Vec<&str> = names
+        .split(':')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .collect();
+
+    if strict {
+        if let Some(name) = names.iter().find(|name| !backends::is_known_backend(name)) {
+            return Err(Error::Error(format!(
+                "Unknown backend {} in the DIALOG environment variable",
+                name
+            )));
+        }
+    }
+
+    for name in names {
+        if backends::is_backend_available(name) {
+            if let Some(backend) = backends::from_str(name) {
+                return Ok(Some(backend));
+            }
         }
     }
+    Ok(None)
+}
 
+fn detect_backend() -> Box<dyn backends::Backend> {
     // Prefer KDialog over Zenity if the user is logged into a KDE session
     let kdialog_available = backends::KDialog::is_available();
     if let Ok(desktop) = env::var("XDG_CURRENT_DESKTOP") {
@@ -376,8 +1830,8 @@ pub fn default_backend() -> Box<dyn backends::Backend> {
 
     if let Ok(display) = env::var("DISPLAY") {
         if !display.is_empty() {
-            if backends::Zenity::is_available() {
-                return Box::new(backends::Zenity::new());
+            if let Some(zenity) = backends::Zenity::detect() {
+                return Box::new(zenity);
             }
 
             // Prefer Zenity over KDialog if the user is not logged into a KDE session
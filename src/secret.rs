@@ -0,0 +1,54 @@
+// Copyright (C) 2019 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: MIT
+
+use std::fmt;
+
+use zeroize::Zeroize;
+
+/// A secret string, for example a password entered by the user.
+///
+/// The memory of the secret is overwritten with zeros when it is dropped.  To avoid leaking the
+/// secret into logs, this type does not implement `Display`, and its `Debug` implementation does
+/// not print the secret.  Use [`expose`][] to access the secret.
+///
+/// # Example
+///
+/// ```
+/// let secret = dialog::Secret::from("hunter2".to_string());
+/// assert_eq!("hunter2", secret.expose());
+/// assert_eq!("Secret([REDACTED])", format!("{:?}", secret));
+/// ```
+///
+/// [`expose`]: #method.expose
+#[derive(Clone, Default, PartialEq)]
+pub struct Secret(String);
+
+impl Secret {
+    /// Returns the secret as a string slice.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns `true` if the secret is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<String> for Secret {
+    fn from(s: String) -> Secret {
+        Secret(s)
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Secret([REDACTED])")
+    }
+}
+
+impl Drop for Secret {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
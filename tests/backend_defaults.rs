@@ -0,0 +1,128 @@
+// Copyright (C) 2019 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: MIT
+
+//! Tests the default implementations of the `Backend` methods using a backend that only
+//! implements the required methods.
+
+use std::cell::RefCell;
+use std::process;
+
+use dialog::backends::Backend;
+use dialog::{Choice, DialogBox, Field, Secret};
+
+/// A backend that returns the given answers and records the number of messages.
+struct Scripted {
+    inputs: RefCell<Vec<Option<&'static str>>>,
+    passwords: RefCell<Vec<Option<&'static str>>>,
+    questions: RefCell<Vec<Choice>>,
+    messages: RefCell<usize>,
+}
+
+impl Scripted {
+    fn new(inputs: Vec<Option<&'static str>>, passwords: Vec<Option<&'static str>>) -> Scripted {
+        Scripted {
+            inputs: RefCell::new(inputs),
+            passwords: RefCell::new(passwords),
+            questions: RefCell::new(Vec::new()),
+            messages: RefCell::new(0),
+        }
+    }
+}
+
+impl AsRef<Scripted> for Scripted {
+    fn as_ref(&self) -> &Self {
+        self
+    }
+}
+
+impl Backend for Scripted {
+    fn show_input(&self, _input: &dialog::Input) -> dialog::Result<Option<String>> {
+        Ok(self.inputs.borrow_mut().remove(0).map(ToOwned::to_owned))
+    }
+
+    fn show_message(&self, _message: &dialog::Message) -> dialog::Result<()> {
+        *self.messages.borrow_mut() += 1;
+        Ok(())
+    }
+
+    fn show_password(&self, _password: &dialog::Password) -> dialog::Result<Option<Secret>> {
+        let password = self.passwords.borrow_mut().remove(0);
+        Ok(password.map(|password| Secret::from(password.to_owned())))
+    }
+
+    fn show_question(&self, _question: &dialog::Question) -> dialog::Result<Choice> {
+        Ok(self.questions.borrow_mut().remove(0))
+    }
+}
+
+#[test]
+fn login() {
+    let backend = Scripted::new(vec![Some("alice")], vec![Some("hunter2")]);
+    let credentials = dialog::Login::new("Log in").show_with(&backend).unwrap();
+    let credentials = credentials.unwrap();
+    assert_eq!("alice", credentials.username());
+    assert_eq!("hunter2", credentials.password().expose());
+
+    let backend = Scripted::new(vec![None], vec![]);
+    assert_eq!(
+        None,
+        dialog::Login::new("Log in").show_with(&backend).unwrap()
+    );
+}
+
+#[test]
+fn form() {
+    let backend = Scripted::new(vec![Some("alice")], vec![Some("hunter2")]);
+    let values = dialog::Form::new("Settings")
+        .field(Field::read_only("Server", "example.org"))
+        .field(Field::text("User"))
+        .field(Field::password("Password"))
+        .show_with(&backend)
+        .unwrap();
+    assert_eq!(
+        Some(vec![
+            "example.org".to_owned(),
+            "alice".to_owned(),
+            "hunter2".to_owned()
+        ]),
+        values
+    );
+}
+
+#[test]
+fn menu() {
+    let backend = Scripted::new(vec![Some("5"), Some(" 2 ")], vec![]);
+    let index = dialog::Menu::new("Color")
+        .item("Red")
+        .item("Green")
+        .show_with(&backend)
+        .unwrap();
+    assert_eq!(Some(1), index);
+}
+
+#[test]
+fn license() {
+    let backend = Scripted::new(vec![], vec![]);
+    backend.questions.borrow_mut().push(Choice::Yes);
+    assert!(dialog::License::new("Terms").show_with(&backend).unwrap());
+}
+
+#[test]
+fn text_viewer() {
+    let backend = Scripted::new(vec![], vec![]);
+    dialog::TextViewer::new("Text").show_with(&backend).unwrap();
+    assert_eq!(1, *backend.messages.borrow());
+}
+
+#[cfg(unix)]
+#[test]
+fn program_box() {
+    let backend = Scripted::new(vec![], vec![]);
+    let mut command = process::Command::new("sh");
+    command.args(["-c", "echo output; exit 3"]);
+    let status = dialog::ProgramBox::new(command)
+        .show_with(&backend)
+        .unwrap();
+    assert_eq!(Some(3), status.code());
+    assert_eq!(1, *backend.messages.borrow());
+}
@@ -0,0 +1,20 @@
+// Copyright (C) 2019 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: MIT
+
+use std::env;
+
+// The tests modify the environment, so they are run in a single test function.
+#[test]
+fn dialog_variable() {
+    env::set_var("DIALOG", "Unknown");
+    assert!(dialog::default_backend_strict().is_err());
+
+    env::set_var("DIALOG", "unknown : stdio");
+    assert!(dialog::default_backend_strict().is_err());
+
+    env::set_var("DIALOG", " editor: STDIO ");
+    assert!(dialog::default_backend_strict().is_ok());
+
+    env::set_var("DIALOG", "");
+    assert!(dialog::default_backend_strict().is_ok());
+}
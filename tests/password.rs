@@ -0,0 +1,139 @@
+// Copyright (C) 2019 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: MIT
+
+//! Tests the confirmation and the policy checks of the password dialog box.
+
+use dialog::backends::{Catalog, Stdio};
+use dialog::{CharClass, DialogBox, Password};
+
+/// Shows the given password dialog box with a `Stdio` backend that reads the given input and
+/// returns the password and the output of the backend.
+fn run(password: &mut Password, input: &str) -> (Option<String>, String) {
+    password.catalog(Catalog::english());
+    let mut output = Vec::new();
+    let result = {
+        let stdio = Stdio::with_io(input.as_bytes(), &mut output);
+        password
+            .show_with(&stdio)
+            .expect("Could not show dialog box")
+    };
+    (
+        result.map(|password| password.expose().to_owned()),
+        String::from_utf8(output).expect("Invalid output"),
+    )
+}
+
+#[test]
+fn min_length() {
+    let mut password = Password::new("Password");
+    password.min_length(4);
+    let (result, output) = run(&mut password, "abc\nabcd\n");
+    assert_eq!(Some("abcd".to_string()), result);
+    assert!(output.contains("The password must be at least 4 characters long."));
+}
+
+#[test]
+fn min_length_counts_characters() {
+    let mut password = Password::new("Password");
+    password.min_length(3);
+    let (result, output) = run(&mut password, "äöü\n");
+    assert_eq!(Some("äöü".to_string()), result);
+    assert!(!output.contains("characters long"));
+}
+
+#[test]
+fn require() {
+    let mut password = Password::new("Password");
+    password
+        .require(CharClass::Uppercase)
+        .require(CharClass::Digit)
+        .require(CharClass::Symbol);
+    let (result, output) = run(&mut password, "abc\nAbc\nAbc1\nAbc1!\n");
+    assert_eq!(Some("Abc1!".to_string()), result);
+    assert!(output.contains("The password must contain an uppercase letter."));
+    assert!(output.contains("The password must contain a digit."));
+    assert!(output.contains("The password must contain a symbol."));
+    assert!(!output.contains("lowercase"));
+}
+
+#[test]
+fn check() {
+    let mut password = Password::new("Password");
+    password.min_length(2).check(|password| match password {
+        "secret" => Err("Too obvious.".to_string()),
+        _ => Ok(()),
+    });
+    let (result, output) = run(&mut password, "a\nsecret\nhunter2\n");
+    assert_eq!(Some("hunter2".to_string()), result);
+    assert!(output.contains("The password must be at least 2 characters long."));
+    assert!(output.contains("Too obvious."));
+}
+
+#[test]
+fn confirm() {
+    let mut password = Password::new("Password");
+    password.confirm("Repeat");
+    let (result, output) = run(&mut password, "one\ntwo\nthree\nthree\n");
+    assert_eq!(Some("three".to_string()), result);
+    assert!(output.contains("The passwords do not match."));
+    assert_eq!(2, output.matches("Repeat: ").count());
+}
+
+#[test]
+fn confirm_cancel() {
+    let mut password = Password::new("Password");
+    password.confirm("Repeat");
+    let (result, _) = run(&mut password, "one\n");
+    assert_eq!(None, result);
+}
+
+#[test]
+fn localized_messages() {
+    let mut password = Password::new("Passwort");
+    password
+        .min_length(4)
+        .require(CharClass::Digit)
+        .confirm("Wiederholen");
+    password.catalog(Catalog::german());
+    let mut output = Vec::new();
+    let result = {
+        let stdio = Stdio::with_io(&b"abc\nabcd\nabc1\nabc2\nabc1\nabc1\n"[..], &mut output);
+        password
+            .show_with(&stdio)
+            .expect("Could not show dialog box")
+    };
+    assert_eq!(
+        Some("abc1"),
+        result.as_ref().map(|password| password.expose())
+    );
+    let output = String::from_utf8(output).expect("Invalid output");
+    assert!(output.contains("Das Passwort muss mindestens 4 Zeichen lang sein."));
+    assert!(output.contains("Das Passwort muss eine Ziffer enthalten."));
+    assert!(output.contains("Die Passwörter stimmen nicht überein."));
+}
+
+#[test]
+fn custom_messages() {
+    let mut catalog = Catalog::english();
+    catalog.set_password_too_short("At least {} characters!");
+    catalog.set_password_missing(CharClass::Lowercase, "Lowercase!");
+    let mut password = Password::new("Password");
+    password
+        .min_length(2)
+        .require(CharClass::Lowercase)
+        .catalog(catalog);
+    let mut output = Vec::new();
+    let result = {
+        let stdio = Stdio::with_io(&b"a\nAB\nab\n"[..], &mut output);
+        password
+            .show_with(&stdio)
+            .expect("Could not show dialog box")
+    };
+    assert_eq!(
+        Some("ab"),
+        result.as_ref().map(|password| password.expose())
+    );
+    let output = String::from_utf8(output).expect("Invalid output");
+    assert!(output.contains("At least 2 characters!"));
+    assert!(output.contains("Lowercase!"));
+}
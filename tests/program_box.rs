@@ -0,0 +1,56 @@
+// Copyright (C) 2019 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: MIT
+
+//! Tests that the program box does not block the program if the backend exits early.
+
+#![cfg(unix)]
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path;
+use std::process;
+use std::sync::mpsc;
+use std::thread;
+use std::time;
+
+use dialog::backends::{Backend, Dialog, Zenity};
+use dialog::{DialogBox, ProgramBox};
+
+/// Creates an executable that exits immediately without reading its input.
+fn fake_backend(name: &str) -> path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("dialog-program-box-{}", process::id()));
+    fs::create_dir_all(&dir).expect("Could not create directory");
+    let path = dir.join(name);
+    fs::write(&path, "#!/bin/sh\nexit 0\n").expect("Could not write fake backend");
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o755))
+        .expect("Could not set permissions");
+    path
+}
+
+/// Shows a program box that writes more output than fits into a pipe and asserts that the program
+/// is terminated instead of blocking forever.
+fn assert_terminates<B: Backend + AsRef<B> + Send + 'static>(backend: B) {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let mut command = process::Command::new("sh");
+        command.args(["-c", "head -c 1000000 /dev/zero; exit 7"]);
+        let status = ProgramBox::new(command).show_with::<B>(&backend);
+        let _ = sender.send(status.map(|status| status.code()));
+    });
+    let status = receiver
+        .recv_timeout(time::Duration::from_secs(10))
+        .expect("The program box did not return");
+    assert_eq!(Some(7), status.expect("Could not show program box"));
+}
+
+#[test]
+fn dialog_exits_early() {
+    let mut dialog = Dialog::new();
+    dialog.set_program(fake_backend("dialog"));
+    assert_terminates(dialog);
+}
+
+#[test]
+fn zenity_exits_early() {
+    assert_terminates(Zenity::with_program(fake_backend("zenity")));
+}
@@ -0,0 +1,17 @@
+// Copyright (C) 2019 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: MIT
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn dialog_boxes_are_send_and_sync() {
+    assert_send_sync::<dialog::Input>();
+    assert_send_sync::<dialog::TypedInput<u32>>();
+    assert_send_sync::<dialog::Password>();
+}
+
+#[test]
+fn stdio_is_send_and_sync() {
+    assert_send_sync::<dialog::backends::Stdio>();
+    assert_send_sync::<dialog::backends::Stdio<&[u8], Vec<u8>>>();
+}
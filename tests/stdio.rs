@@ -0,0 +1,82 @@
+// Copyright (C) 2019 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: MIT
+
+//! Tests the `Stdio` backend by reading the answers from a string.
+
+use dialog::backends::{Catalog, Stdio};
+use dialog::{Choice, DialogBox, Input, Question};
+
+/// Shows the given dialog box with a `Stdio` backend that reads the given input and returns the
+/// result and the output of the backend.
+fn run<D: DialogBox>(dialog: &D, input: &str) -> (D::Output, String) {
+    let mut output = Vec::new();
+    let result = {
+        let mut stdio = Stdio::with_io(input.as_bytes(), &mut output);
+        stdio.set_catalog(Catalog::english());
+        dialog.show_with(&stdio).expect("Could not show dialog box")
+    };
+    (result, String::from_utf8(output).expect("Invalid output"))
+}
+
+#[test]
+fn question_reprompts_after_typo() {
+    let (choice, output) = run(&Question::new("Continue?"), "yse\nyes\n");
+    assert_eq!(Choice::Yes, choice);
+    assert_eq!(2, output.matches("Continue? [y/n]: ").count());
+    assert!(output.contains("Please answer yes or no."));
+}
+
+#[test]
+fn question_empty_line_with_default() {
+    let mut question = Question::new("Continue?");
+    question.default(Choice::No);
+    let (choice, output) = run(&question, "\n");
+    assert_eq!(Choice::No, choice);
+    assert!(!output.contains("Please answer yes or no."));
+}
+
+#[test]
+fn question_empty_line_without_default() {
+    let (choice, output) = run(&Question::new("Continue?"), "\nn\n");
+    assert_eq!(Choice::No, choice);
+    assert!(output.contains("Please answer yes or no."));
+}
+
+#[test]
+fn question_crlf() {
+    let (choice, _) = run(&Question::new("Continue?"), "y\r\n");
+    assert_eq!(Choice::Yes, choice);
+}
+
+#[test]
+fn question_eof() {
+    let (choice, _) = run(&Question::new("Continue?"), "");
+    assert_eq!(Choice::Cancel, choice);
+}
+
+#[test]
+fn input_empty_line_with_default() {
+    let mut input = Input::new("Name");
+    input.default("Alice");
+    let (name, output) = run(&input, "\n");
+    assert_eq!(Some("Alice".to_string()), name);
+    assert_eq!("Name [default: Alice]: ", output);
+}
+
+#[test]
+fn input_empty_line_without_default() {
+    let (name, _) = run(&Input::new("Name"), "\n");
+    assert_eq!(Some(String::new()), name);
+}
+
+#[test]
+fn input_crlf() {
+    let (name, _) = run(&Input::new("Name"), "Bob\r\n");
+    assert_eq!(Some("Bob".to_string()), name);
+}
+
+#[test]
+fn input_eof() {
+    let (name, _) = run(&Input::new("Name"), "");
+    assert_eq!(None, name);
+}